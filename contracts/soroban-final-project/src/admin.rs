@@ -1,18 +1,89 @@
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{panic_with_error, vec, Address, Env, Vec};
 
-use crate::storage_types::DataKey;
+use crate::error::{GovernanceError, TokenError};
+use crate::storage_types::{AdminSet, DataKey, PendingAdmin, MAX_ADMIN_SIGNERS};
 
 pub fn has_administrator(e: &Env) -> bool {
-    let key = DataKey::Admin;
-    e.storage().instance().has(&key)
+    e.storage().instance().has(&DataKey::AdminSet) || e.storage().instance().has(&DataKey::Admin)
 }
 
-pub fn read_administrator(e: &Env) -> Address {
-    let key = DataKey::Admin;
-    e.storage().instance().get(&key).unwrap()
+/// Reads the admin set. A single admin stored by an older version reads as
+/// a 1-of-1 set.
+pub fn read_admin_set(e: &Env) -> AdminSet {
+    if let Some(admin_set) = e.storage().instance().get(&DataKey::AdminSet) {
+        return admin_set;
+    }
+    let admin: Address = e
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap_or_else(|| panic_with_error!(e, TokenError::NotInitialized));
+    AdminSet {
+        signers: vec![e, admin],
+        threshold: 1,
+    }
 }
 
-pub fn write_administrator(e: &Env, id: &Address) {
-    let key = DataKey::Admin;
-    e.storage().instance().set(&key, id);
-}
\ No newline at end of file
+pub fn check_admin_set(e: &Env, admin_set: &AdminSet) {
+    let count = admin_set.signers.len();
+    if count == 0
+        || count > MAX_ADMIN_SIGNERS
+        || admin_set.threshold == 0
+        || admin_set.threshold > count
+    {
+        panic_with_error!(e, GovernanceError::InvalidAdminSet);
+    }
+    for (i, signer) in admin_set.signers.iter().enumerate() {
+        if admin_set.signers.first_index_of(&signer) != Some(i as u32) {
+            panic_with_error!(e, GovernanceError::DuplicateAdminSigner);
+        }
+    }
+}
+
+pub fn write_admin_set(e: &Env, admin_set: &AdminSet) {
+    check_admin_set(e, admin_set);
+    e.storage().instance().set(&DataKey::AdminSet, admin_set);
+    e.storage().instance().remove(&DataKey::Admin);
+}
+
+/// Requires every address in `signers` to be a distinct member of the admin
+/// set and to have authorized the call, and at least `threshold` of them.
+pub fn require_admin(e: &Env, signers: &Vec<Address>) {
+    let admin_set = read_admin_set(e);
+    for (i, signer) in signers.iter().enumerate() {
+        if !admin_set.signers.contains(&signer) {
+            panic_with_error!(e, GovernanceError::NotAnAdminSigner);
+        }
+        if signers.first_index_of(&signer) != Some(i as u32) {
+            panic_with_error!(e, GovernanceError::DuplicateAdminSigner);
+        }
+    }
+    if signers.len() < admin_set.threshold {
+        panic_with_error!(e, GovernanceError::InsufficientAdminSigners);
+    }
+    for signer in signers.iter() {
+        signer.require_auth();
+    }
+}
+
+/// Whether `addr` can act as the administrator on its own, which is only the
+/// case for a member of a set with threshold 1.
+pub fn is_sole_admin(e: &Env, addr: &Address) -> bool {
+    let admin_set = read_admin_set(e);
+    admin_set.threshold == 1 && admin_set.signers.contains(addr)
+}
+
+pub fn read_pending_admin(e: &Env) -> Option<PendingAdmin> {
+    let key = DataKey::PendingAdmin;
+    e.storage().instance().get(&key)
+}
+
+pub fn write_pending_admin(e: &Env, pending: &PendingAdmin) {
+    let key = DataKey::PendingAdmin;
+    e.storage().instance().set(&key, pending);
+}
+
+pub fn remove_pending_admin(e: &Env) {
+    let key = DataKey::PendingAdmin;
+    e.storage().instance().remove(&key);
+}
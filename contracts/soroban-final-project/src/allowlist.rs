@@ -0,0 +1,65 @@
+use crate::error::TokenError;
+use crate::storage_types::{DataKey, BALANCE_BUMP_AMOUNT, BALANCE_LIFETIME_THRESHOLD};
+use soroban_sdk::{panic_with_error, Address, Env};
+
+pub fn is_allowlist_mode(e: &Env) -> bool {
+    e.storage()
+        .instance()
+        .get::<DataKey, bool>(&DataKey::AllowlistMode)
+        .unwrap_or(false)
+}
+
+pub fn enable_allowlist_mode(e: &Env) {
+    e.storage().instance().set(&DataKey::AllowlistMode, &true);
+}
+
+/// Returns the ledger at which the approval of `addr` lapses (0 for none),
+/// or `None` if `addr` is not on the allowlist.
+pub fn read_allowlist_entry(e: &Env, addr: Address) -> Option<u32> {
+    let key = DataKey::Allowlisted(addr);
+    let expiration_ledger = e.storage().persistent().get::<DataKey, u32>(&key)?;
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    Some(expiration_ledger)
+}
+
+pub fn write_allowlist_entry(e: &Env, addr: Address, expiration_ledger: u32) {
+    let key = DataKey::Allowlisted(addr);
+    e.storage().persistent().set(&key, &expiration_ledger);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+pub fn remove_allowlist_entry(e: &Env, addr: Address) -> bool {
+    let key = DataKey::Allowlisted(addr);
+    let existed = e.storage().persistent().has(&key);
+    e.storage().persistent().remove(&key);
+    existed
+}
+
+/// Whether `addr` may hold the token. Always true outside allowlist mode.
+pub fn is_allowed(e: &Env, addr: &Address) -> bool {
+    if !is_allowlist_mode(e) {
+        return true;
+    }
+    match read_allowlist_entry(e, addr.clone()) {
+        Some(expiration_ledger) => {
+            expiration_ledger == 0 || e.ledger().sequence() < expiration_ledger
+        }
+        None => false,
+    }
+}
+
+pub fn require_sender_allowed(e: &Env, addr: &Address) {
+    if !is_allowed(e, addr) {
+        panic_with_error!(e, TokenError::SenderNotAllowed);
+    }
+}
+
+pub fn require_recipient_allowed(e: &Env, addr: &Address) {
+    if !is_allowed(e, addr) {
+        panic_with_error!(e, TokenError::RecipientNotAllowed);
+    }
+}
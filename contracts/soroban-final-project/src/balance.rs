@@ -1,5 +1,6 @@
+use crate::error::TokenError;
 use crate::storage_types::{DataKey, BALANCE_BUMP_AMOUNT, BALANCE_LIFETIME_THRESHOLD};
-use soroban_sdk::{Address, Env};
+use soroban_sdk::{panic_with_error, Address, Env};
 
 pub fn read_balance(e: &Env, addr: Address) -> i128 {
     let key = DataKey::Balance(addr);
@@ -29,7 +30,21 @@ pub fn receive_balance(e: &Env, addr: Address, amount: i128) {
 pub fn spend_balance(e: &Env, addr: Address, amount: i128) {
     let balance = read_balance(e, addr.clone());
     if balance < amount {
-        panic!("insufficient balance");
+        panic_with_error!(e, TokenError::InsufficientBalance);
+    }
+    let locked = crate::lock::read_locked_amount(e, addr.clone());
+    if balance - amount < locked {
+        panic_with_error!(e, TokenError::BalanceLocked);
+    }
+    write_balance(e, addr, balance - amount);
+}
+
+/// Removes `amount` from `addr` without honouring amount locks. Only used by
+/// administrative seizures (clawback and forced transfer).
+pub fn seize_balance(e: &Env, addr: Address, amount: i128) {
+    let balance = read_balance(e, addr.clone());
+    if balance < amount {
+        panic_with_error!(e, TokenError::InsufficientBalance);
     }
     write_balance(e, addr, balance - amount);
-}
\ No newline at end of file
+}
@@ -0,0 +1,81 @@
+use crate::error::TokenError;
+use crate::storage_types::{
+    CompliancePolicy, ComplianceRecord, ComplianceStatus, DataKey, BALANCE_BUMP_AMOUNT,
+    BALANCE_LIFETIME_THRESHOLD, MAX_COMPLIANCE_HISTORY,
+};
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
+
+/// Returns the status changes recorded for `addr`, oldest first. Only the
+/// latest `MAX_COMPLIANCE_HISTORY` changes are kept.
+pub fn read_compliance_history(e: &Env, addr: Address) -> Vec<ComplianceRecord> {
+    let key = DataKey::ComplianceHistory(addr);
+    if let Some(history) = e.storage().persistent().get::<DataKey, Vec<ComplianceRecord>>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+        history
+    } else {
+        Vec::new(e)
+    }
+}
+
+pub fn read_compliance_record(e: &Env, addr: Address) -> Option<ComplianceRecord> {
+    read_compliance_history(e, addr).last()
+}
+
+pub fn read_compliance_status(e: &Env, addr: Address) -> ComplianceStatus {
+    read_compliance_record(e, addr)
+        .map(|record| record.status)
+        .unwrap_or(ComplianceStatus::None)
+}
+
+pub fn write_compliance_record(e: &Env, addr: Address, record: ComplianceRecord) {
+    let mut history = read_compliance_history(e, addr.clone());
+    if history.len() >= MAX_COMPLIANCE_HISTORY {
+        history.pop_front();
+    }
+    history.push_back(record);
+
+    let key = DataKey::ComplianceHistory(addr);
+    e.storage().persistent().set(&key, &history);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+/// Statuses without a configured policy allow every operation.
+pub fn read_policy(e: &Env, status: ComplianceStatus) -> CompliancePolicy {
+    e.storage()
+        .instance()
+        .get::<DataKey, CompliancePolicy>(&DataKey::CompliancePolicy(status))
+        .unwrap_or(CompliancePolicy {
+            can_send: true,
+            can_receive: true,
+            send_limit: None,
+        })
+}
+
+pub fn write_policy(e: &Env, status: ComplianceStatus, policy: &CompliancePolicy) {
+    e.storage()
+        .instance()
+        .set(&DataKey::CompliancePolicy(status), policy);
+}
+
+/// Checks that the policy for `addr`'s status lets it send or burn `amount`.
+pub fn require_can_send(e: &Env, addr: &Address, amount: i128) {
+    let policy = read_policy(e, read_compliance_status(e, addr.clone()));
+    if !policy.can_send {
+        panic_with_error!(e, TokenError::SenderNotCompliant);
+    }
+    if policy.send_limit.is_some_and(|limit| amount > limit) {
+        panic_with_error!(e, TokenError::ComplianceLimitExceeded);
+    }
+}
+
+/// Checks that the policy for `addr`'s status lets it receive tokens.
+pub fn require_can_receive(e: &Env, addr: &Address) {
+    let policy = read_policy(e, read_compliance_status(e, addr.clone()));
+    if !policy.can_receive {
+        panic_with_error!(e, TokenError::RecipientNotCompliant);
+    }
+}
@@ -0,0 +1,54 @@
+use crate::error::TokenError;
+use crate::storage_types::DataKey;
+use soroban_sdk::{contractclient, panic_with_error, Address, Env};
+
+/// Interface expected from an external compliance contract. `from` is `None`
+/// for mints and `to` is `None` for burns.
+#[allow(dead_code)]
+#[contractclient(name = "ComplianceContractClient")]
+pub trait ComplianceContractInterface {
+    fn can_transfer(
+        env: Env,
+        token: Address,
+        from: Option<Address>,
+        to: Option<Address>,
+        amount: i128,
+    ) -> bool;
+
+    fn transferred(
+        env: Env,
+        token: Address,
+        from: Option<Address>,
+        to: Option<Address>,
+        amount: i128,
+    );
+}
+
+pub fn read_compliance_contract(e: &Env) -> Option<Address> {
+    e.storage().instance().get(&DataKey::ComplianceContract)
+}
+
+pub fn write_compliance_contract(e: &Env, contract: &Option<Address>) {
+    match contract {
+        Some(address) => e.storage().instance().set(&DataKey::ComplianceContract, address),
+        None => e.storage().instance().remove(&DataKey::ComplianceContract),
+    }
+}
+
+/// Asks the configured compliance contract, if any, to approve the movement.
+pub fn require_approved(e: &Env, from: Option<Address>, to: Option<Address>, amount: i128) {
+    if let Some(contract) = read_compliance_contract(e) {
+        let client = ComplianceContractClient::new(e, &contract);
+        if !client.can_transfer(&e.current_contract_address(), &from, &to, &amount) {
+            panic_with_error!(e, TokenError::ComplianceContractDenied);
+        }
+    }
+}
+
+/// Notifies the configured compliance contract, if any, of a completed movement.
+pub fn notify_transferred(e: &Env, from: Option<Address>, to: Option<Address>, amount: i128) {
+    if let Some(contract) = read_compliance_contract(e) {
+        let client = ComplianceContractClient::new(e, &contract);
+        client.transferred(&e.current_contract_address(), &from, &to, &amount);
+    }
+}
@@ -1,181 +1,1504 @@
+use crate::error::{GovernanceError, TokenError};
+use crate::freeze::{
+    get_active_freeze_info, is_account_effectively_frozen, is_inbound_frozen, is_outbound_frozen,
+};
 use crate::storage_types::{
-    DataKey, FreezeInfo, ComplianceStatus, INSTANCE_BUMP_AMOUNT, INSTANCE_LIFETIME_THRESHOLD,
+    AdminSet, AmountLock, CompliancePolicy, ComplianceRecord, ComplianceStatus, EscalationRule,
+    EscalationTrigger, FreezeHistoryEntry, FreezeInfo, FreezeScope, FrozenAccountsPage,
+    DualControlConfig, MinterAllowance, PauseInfo, PendingAdmin, Proposal, ProposalStatus,
+    ProposedAction, Role, SupplyCap, VelocityLimit, VelocityUsage,
+    CURRENT_SCHEMA_VERSION, INSTANCE_BUMP_AMOUNT, INSTANCE_LIFETIME_THRESHOLD,
+    MAX_ALLOWLIST_BATCH, MAX_FREEZE_BATCH,
 };
 use soroban_sdk::{
-    contract, contractimpl, symbol_short,
-    Address, Env, IntoVal, String, Symbol, Val, Vec,
+    contract, contractimpl, panic_with_error, symbol_short, vec,
+    Address, BytesN, Env, IntoVal, String, Symbol, Val, Vec,
 };
 use soroban_sdk::token::Interface as StandardTokenInterface;
 
 
-fn check_nonnegative_amount(amount: i128) {
-    if amount < 0 {
-        panic!("negative amount is not allowed: {}", amount)
+fn check_nonnegative_amount(e: &Env, amount: i128) {
+    if amount < 0 {
+        panic_with_error!(e, TokenError::NegativeAmount);
+    }
+}
+
+/// The tag of an active freeze, falling back to the registered compliance
+/// status for accounts that are not frozen.
+fn active_compliance_tag(e: &Env, account: &Address) -> ComplianceStatus {
+    if let Some(info) = get_active_freeze_info(e, account) {
+        info.compliance_tag
+    } else {
+        crate::compliance::read_compliance_status(e, account.clone())
+    }
+}
+
+/// Checks that `from` may send or burn `amount`: not frozen outbound and
+/// allowed by the policy for its compliance status.
+fn require_can_send(e: &Env, from: &Address, amount: i128) {
+    if is_outbound_frozen(e, from) {
+        panic_with_error!(e, TokenError::AccountFrozen);
+    }
+    crate::compliance::require_can_send(e, from, amount);
+}
+
+/// Checks that `to` may receive tokens: not frozen inbound and allowed by
+/// the policy for its compliance status.
+fn require_can_receive(e: &Env, to: &Address) {
+    if is_inbound_frozen(e, to) {
+        panic_with_error!(e, TokenError::RecipientFrozen);
+    }
+    crate::compliance::require_can_receive(e, to);
+}
+
+/// The ledger `duration_ledgers` from now, or 0 (no expiry) for a zero
+/// duration. Shared by freezes, amount locks and pauses.
+fn expiration_ledger_after(e: &Env, duration_ledgers: u32) -> u32 {
+    if duration_ledgers == 0 {
+        0
+    } else {
+        e.ledger()
+            .sequence()
+            .checked_add(duration_ledgers)
+            .unwrap_or_else(|| panic_with_error!(e, TokenError::DurationOverflow))
+    }
+}
+
+fn emit_freeze_event(
+    e: &Env,
+    event_type_symbol: Symbol,
+    operator: Address,
+    account: Address,
+    freeze_info: Option<FreezeInfo>,
+) {
+    let mut topics: Vec<Val> = Vec::new(e);
+    topics.push_back(event_type_symbol.into_val(e));
+    topics.push_back(symbol_short!("admin").into_val(e));
+    topics.push_back(symbol_short!("account").into_val(e));
+
+    let mut data: Vec<Val> = Vec::new(e);
+    data.push_back(operator.into_val(e));
+    data.push_back(account.into_val(e));
+
+    if let Some(info) = freeze_info {
+        if info.start_ledger > e.ledger().sequence() {
+            topics.push_back(symbol_short!("starts").into_val(e));
+            data.push_back(info.start_ledger.into_val(e));
+        }
+        if info.expiration_ledger != 0 {
+            topics.push_back(symbol_short!("expires").into_val(e));
+            data.push_back(info.expiration_ledger.into_val(e));
+        }
+        if info.expires_at != 0 {
+            topics.push_back(Symbol::new(e, "expires_at").into_val(e));
+            data.push_back(info.expires_at.into_val(e));
+        }
+        topics.push_back(symbol_short!("reason").into_val(e));
+        data.push_back(info.reason.clone().into_val(e));
+        topics.push_back(symbol_short!("scope").into_val(e));
+        data.push_back(info.scope.into_val(e));
+
+        if info.compliance_tag != ComplianceStatus::None {
+            topics.push_back(Symbol::new(e, "compliance").into_val(e));
+            data.push_back(info.compliance_tag.into_val(e));
+        }
+    }
+    e.events().publish(topics, data);
+}
+
+/// Freezes `account`, replacing any freeze it has. While dual control is on,
+/// an active freeze can only be lifted through a proposal, so it cannot be
+/// replaced by one that might end sooner or cover less either.
+fn freeze_one(e: &Env, freezer: &Address, account: Address, freeze_info: FreezeInfo) {
+    if is_account_effectively_frozen(e, &account) {
+        crate::proposal::require_single_key_allowed(e);
+    }
+    crate::freeze::apply_freeze(e, freezer.clone(), account.clone(), &freeze_info);
+    emit_freeze_event(
+        e,
+        Symbol::new(e, "freeze_acc"),
+        freezer.clone(),
+        account,
+        Some(freeze_info),
+    );
+}
+
+/// Freezes the accounts targeted by escalation rules that fire for a
+/// completed transfer. Accounts already frozen are left untouched.
+fn apply_escalation_rules(e: &Env, from: &Address, to: &Address, amount: i128) {
+    let rules = crate::escalation::read_rules(e);
+    if rules.is_empty() {
+        return;
+    }
+    let from_tag = active_compliance_tag(e, from);
+    for rule in rules.iter() {
+        let Some(account) = crate::escalation::rule_target(&rule, from, from_tag, to, amount)
+        else {
+            continue;
+        };
+        if is_account_effectively_frozen(e, &account) {
+            continue;
+        }
+        let freeze_info = FreezeInfo {
+            start_ledger: e.ledger().sequence(),
+            expiration_ledger: expiration_ledger_after(e, rule.duration_ledgers),
+            expires_at: 0,
+            reason: crate::escalation::freeze_reason(e, &rule),
+            compliance_tag: rule.compliance_tag,
+            scope: rule.scope,
+        };
+        freeze_one(e, &e.current_contract_address(), account.clone(), freeze_info);
+        e.events().publish(
+            (Symbol::new(e, "auto_freeze"), account),
+            rule.id,
+        );
+    }
+}
+
+fn unfreeze_one(e: &Env, freezer: &Address, account: Address) {
+    if crate::freeze::lift_freeze(e, freezer.clone(), account.clone()) {
+        emit_freeze_event(e, Symbol::new(e, "unfrz_acc"), freezer.clone(), account, None);
+    }
+}
+
+fn release_lock(e: &Env, freezer: Address, account: Address, lock_id: u32) {
+    let lock = crate::lock::remove_lock(e, account.clone(), lock_id);
+    e.events().publish(
+        (Symbol::new(e, "release_amt"), freezer, account),
+        (lock_id, lock.amount),
+    );
+}
+
+fn amend_freeze(
+    e: &Env,
+    freezer: Address,
+    account: Address,
+    old_info: FreezeInfo,
+    new_info: FreezeInfo,
+) {
+    crate::freeze::amend_freeze(e, freezer.clone(), account.clone(), &new_info);
+    e.events().publish(
+        (Symbol::new(e, "freeze_amended"), account),
+        (freezer, old_info, new_info),
+    );
+}
+
+fn initialize_token(e: &Env, admin: Address, decimal: u32, name: String, symbol: String) {
+    if crate::admin::has_administrator(e) {
+        panic_with_error!(e, TokenError::AlreadyInitialized);
+    }
+    crate::admin::write_admin_set(
+        e,
+        &AdminSet {
+            signers: vec![e, admin],
+            threshold: 1,
+        },
+    );
+    if decimal > u8::MAX.into() {
+        panic_with_error!(e, TokenError::DecimalTooLarge);
+    }
+    crate::metadata::write_metadata(
+        e,
+        soroban_token_sdk::metadata::TokenMetadata { decimal, name, symbol },
+    );
+    crate::upgrade::write_schema_version(e, CURRENT_SCHEMA_VERSION);
+    e.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+fn check_velocity_limit(e: &Env, limit: &Option<VelocityLimit>) {
+    if let Some(limit) = limit {
+        check_nonnegative_amount(e, limit.amount);
+        if limit.window_ledgers == 0 {
+            panic_with_error!(e, TokenError::InvalidVelocityWindow);
+        }
+    }
+}
+
+fn mint_to(e: &Env, minter: Address, to: Address, amount: i128) {
+    crate::pause::require_not_paused(e);
+    require_can_receive(e, &to);
+    crate::allowlist::require_recipient_allowed(e, &to);
+    crate::compliance_hook::require_approved(e, None, Some(to.clone()), amount);
+    crate::supply::increase_supply(e, amount);
+    crate::balance::receive_balance(e, to.clone(), amount);
+    crate::compliance_hook::notify_transferred(e, None, Some(to.clone()), amount);
+    e.events().publish((Symbol::new(e, "mint"), minter, to), amount);
+}
+
+fn clawback_from(e: &Env, operators: Vec<Address>, from: Address, amount: i128, reason: String) {
+    crate::balance::seize_balance(e, from.clone(), amount);
+    crate::supply::decrease_supply(e, amount);
+    let compliance_tag = active_compliance_tag(e, &from);
+    e.events().publish(
+        (Symbol::new(e, "clawback"), operators, from),
+        (amount, reason, compliance_tag),
+    );
+}
+
+/// Carries out an approved proposal on behalf of its proposer.
+fn execute_proposal(e: &Env, proposal: Proposal) {
+    let proposer = proposal.proposer;
+    match proposal.action.clone() {
+        ProposedAction::Mint(to, amount) => mint_to(e, proposer, to, amount),
+        ProposedAction::Unfreeze(account) => unfreeze_one(e, &proposer, account),
+        ProposedAction::Clawback(from, amount, reason) => {
+            clawback_from(e, vec![e, proposer], from, amount, reason)
+        }
+        ProposedAction::ReleaseAmount(account, lock_id) => {
+            release_lock(e, proposer, account, lock_id)
+        }
+    }
+    e.events().publish(
+        (Symbol::new(e, "proposal_executed"), proposal.id),
+        proposal.action,
+    );
+}
+
+#[contract]
+pub struct Token;
+
+#[contractimpl]
+impl Token {
+    pub fn initialize(e: Env, admin: Address, decimal: u32, name: String, symbol: String) {
+        initialize_token(&e, admin, decimal, name, symbol);
+    }
+
+    /// Initializes the token in allowlist mode: `transfer`, `transfer_from`
+    /// and `mint` then require every party to be on the allowlist. The mode
+    /// cannot be changed afterwards.
+    pub fn initialize_with_allowlist(
+        e: Env,
+        admin: Address,
+        decimal: u32,
+        name: String,
+        symbol: String,
+    ) {
+        initialize_token(&e, admin, decimal, name, symbol);
+        crate::allowlist::enable_allowlist_mode(&e);
+    }
+
+    /// Mints `amount` to `to`. `signers` is a single minter while the admin
+    /// set has threshold 1, and an admin quorum otherwise; the same applies
+    /// to the freeze and unfreeze calls with the freezer role.
+    pub fn mint(e: Env, signers: Vec<Address>, to: Address, amount: i128) {
+        check_nonnegative_amount(&e, amount);
+        let minter = crate::roles::require_role_or_admin(&e, &signers, Role::Minter);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::proposal::require_single_key_allowed(&e);
+        mint_to(&e, minter, to, amount);
+    }
+
+    /// Mints on behalf of a delegated minter, deducting `amount` from the
+    /// quota granted with `set_minter`.
+    pub fn mint_as(e: Env, minter: Address, to: Address, amount: i128) {
+        check_nonnegative_amount(&e, amount);
+        minter.require_auth();
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::proposal::require_single_key_allowed(&e);
+        let remaining = crate::minter::spend_quota(&e, minter.clone(), amount);
+        mint_to(&e, minter.clone(), to, amount);
+        e.events().publish(
+            (Symbol::new(&e, "minter_quota"), minter),
+            remaining,
+        );
+    }
+
+    /// Delegates issuance of up to `quota` tokens to `minter` until
+    /// `expiration_ledger` (0 for no expiry), replacing any earlier quota.
+    pub fn set_minter(
+        e: Env,
+        signers: Vec<Address>,
+        minter: Address,
+        quota: i128,
+        expiration_ledger: u32,
+    ) {
+        crate::admin::require_admin(&e, &signers);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        check_nonnegative_amount(&e, quota);
+        if expiration_ledger != 0 && expiration_ledger <= e.ledger().sequence() {
+            panic_with_error!(&e, TokenError::InvalidExpirationLedger);
+        }
+        crate::minter::write_minter(
+            &e,
+            minter.clone(),
+            &MinterAllowance {
+                quota,
+                expiration_ledger,
+            },
+        );
+        e.events().publish(
+            (Symbol::new(&e, "minter_set"), signers, minter),
+            (quota, expiration_ledger),
+        );
+    }
+
+    pub fn remove_minter(e: Env, signers: Vec<Address>, minter: Address) {
+        crate::admin::require_admin(&e, &signers);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if !crate::minter::remove_minter(&e, minter.clone()) {
+            panic_with_error!(&e, TokenError::NotAMinter);
+        }
+        e.events().publish(
+            (Symbol::new(&e, "minter_removed"), signers, minter),
+            (),
+        );
+    }
+
+    /// The amount `minter` can still issue through `mint_as`.
+    pub fn minter_quota(e: Env, minter: Address) -> i128 {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::minter::read_quota(&e, minter)
+    }
+
+    /// Enables dual control with the given threshold and approval window, or
+    /// disables it with `None`. Proposals already open keep the threshold
+    /// they were created with.
+    pub fn set_dual_control(e: Env, signers: Vec<Address>, config: Option<DualControlConfig>) {
+        crate::admin::require_admin(&e, &signers);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::proposal::write_config(&e, &config);
+        e.events()
+            .publish((Symbol::new(&e, "dual_control_set"), signers), config);
+    }
+
+    pub fn dual_control(e: Env) -> Option<DualControlConfig> {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::proposal::read_config(&e)
+    }
+
+    /// Opens a proposal for `action` and returns its id. The action runs once
+    /// enough approvers other than the proposer have approved it.
+    pub fn propose(e: Env, proposer: Address, action: ProposedAction) -> u32 {
+        crate::roles::require_role(&e, &proposer, Role::Proposer);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        match &action {
+            ProposedAction::Mint(_, amount) | ProposedAction::Clawback(_, amount, _) => {
+                check_nonnegative_amount(&e, *amount)
+            }
+            ProposedAction::Unfreeze(_) | ProposedAction::ReleaseAmount(_, _) => {}
+        }
+        let proposal = crate::proposal::create_proposal(&e, proposer.clone(), action.clone());
+        e.events().publish(
+            (Symbol::new(&e, "proposal_created"), proposal.id),
+            (proposer, action, proposal.expiration_ledger),
+        );
+        proposal.id
+    }
+
+    /// Approves a pending proposal, executing it when this approval meets the
+    /// threshold.
+    pub fn approve_proposal(e: Env, approver: Address, proposal_id: u32) {
+        crate::roles::require_role(&e, &approver, Role::Approver);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let proposal = crate::proposal::approve_proposal(&e, proposal_id, approver.clone());
+        e.events().publish(
+            (Symbol::new(&e, "proposal_approved"), proposal_id),
+            (approver, proposal.approvals.len()),
+        );
+        if proposal.status == ProposalStatus::Executed {
+            execute_proposal(&e, proposal);
+        }
+    }
+
+    pub fn reject_proposal(e: Env, approver: Address, proposal_id: u32) {
+        crate::roles::require_role(&e, &approver, Role::Approver);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::proposal::close_proposal(&e, proposal_id, ProposalStatus::Rejected);
+        e.events().publish(
+            (Symbol::new(&e, "proposal_rejected"), proposal_id),
+            approver,
+        );
+    }
+
+    /// Withdraws a pending proposal. Only its proposer can cancel it.
+    pub fn cancel_proposal(e: Env, proposal_id: u32) {
+        let proposer = crate::proposal::read_proposal(&e, proposal_id).proposer;
+        proposer.require_auth();
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::proposal::close_proposal(&e, proposal_id, ProposalStatus::Cancelled);
+        e.events().publish(
+            (Symbol::new(&e, "proposal_cancelled"), proposal_id),
+            proposer,
+        );
+    }
+
+    pub fn get_proposal(e: Env, proposal_id: u32) -> Proposal {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::proposal::read_proposal(&e, proposal_id)
+    }
+
+    pub fn upgrade(e: Env, signers: Vec<Address>, new_wasm_hash: BytesN<32>) {
+        crate::admin::require_admin(&e, &signers);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        e.deployer().update_current_contract_wasm(new_wasm_hash.clone());
+        e.events().publish(
+            (Symbol::new(&e, "upgraded"), signers),
+            new_wasm_hash,
+        );
+    }
+
+    /// Brings stored data up to the schema of the running code. Meant to be
+    /// called right after `upgrade`. Deployments that predate supply tracking
+    /// must pass their current total supply as `legacy_total_supply`; it is
+    /// ignored once the supply is tracked.
+    pub fn migrate(e: Env, signers: Vec<Address>, legacy_total_supply: Option<i128>) -> u32 {
+        crate::admin::require_admin(&e, &signers);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let from_version = crate::upgrade::read_schema_version(&e);
+        let to_version = crate::upgrade::run_migrations(&e, legacy_total_supply);
+        if to_version != from_version {
+            e.events().publish(
+                (Symbol::new(&e, "migrated"), signers),
+                (from_version, to_version),
+            );
+        }
+        to_version
+    }
+
+    pub fn version(e: Env) -> u32 {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::upgrade::read_schema_version(&e)
+    }
+
+    /// Starts an admin transfer with no delay. The new admin only takes over
+    /// once it calls `accept_admin`.
+    pub fn set_admin(e: Env, signers: Vec<Address>, new_admin: Address) {
+        Self::propose_admin(e, signers, new_admin, 0);
+    }
+
+    /// Proposes handing the admin role to a single address. Only a
+    /// single-signer admin set can be transferred this way; an M-of-N set is
+    /// rotated through `set_admin_signers` so its threshold is never dropped.
+    pub fn propose_admin(e: Env, signers: Vec<Address>, new_admin: Address, delay_ledgers: u32) {
+        crate::admin::require_admin(&e, &signers);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if crate::admin::read_admin_set(&e).threshold > 1 {
+            panic_with_error!(&e, GovernanceError::AdminTransferRequiresSingleSigner);
+        }
+
+        let accept_after_ledger = e
+            .ledger()
+            .sequence()
+            .checked_add(delay_ledgers)
+            .unwrap_or_else(|| panic_with_error!(&e, TokenError::InvalidDelay));
+        crate::admin::write_pending_admin(
+            &e,
+            &PendingAdmin {
+                new_admin: new_admin.clone(),
+                accept_after_ledger,
+            },
+        );
+        e.events().publish(
+            (Symbol::new(&e, "admin_proposed"), signers, new_admin),
+            accept_after_ledger,
+        );
+    }
+
+    /// Completes a pending transfer. Refused if the threshold was raised
+    /// after the proposal, since accepting would collapse it to one key.
+    pub fn accept_admin(e: Env) {
+        let pending = crate::admin::read_pending_admin(&e)
+            .unwrap_or_else(|| panic_with_error!(&e, TokenError::NoPendingAdmin));
+        pending.new_admin.require_auth();
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if e.ledger().sequence() < pending.accept_after_ledger {
+            panic_with_error!(&e, TokenError::AdminDelayNotElapsed);
+        }
+
+        let admin_set = crate::admin::read_admin_set(&e);
+        if admin_set.threshold > 1 {
+            panic_with_error!(&e, GovernanceError::AdminTransferRequiresSingleSigner);
+        }
+        crate::admin::write_admin_set(
+            &e,
+            &AdminSet {
+                signers: vec![&e, pending.new_admin.clone()],
+                threshold: 1,
+            },
+        );
+        crate::admin::remove_pending_admin(&e);
+        e.events().publish(
+            (Symbol::new(&e, "set_admin"), admin_set.signers, pending.new_admin),
+            Val::VOID,
+        );
+    }
+
+    pub fn cancel_admin_proposal(e: Env, signers: Vec<Address>) {
+        crate::admin::require_admin(&e, &signers);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let pending = crate::admin::read_pending_admin(&e)
+            .unwrap_or_else(|| panic_with_error!(&e, TokenError::NoPendingAdmin));
+        crate::admin::remove_pending_admin(&e);
+        e.events().publish(
+            (Symbol::new(&e, "admin_cancelled"), signers, pending.new_admin),
+            Val::VOID,
+        );
+    }
+
+    /// Replaces the admin signers and threshold. Besides `threshold` current
+    /// signers, every newly added signer must authorize, so the set cannot be
+    /// handed to keys nobody controls. Clears any pending admin proposal.
+    pub fn set_admin_signers(
+        e: Env,
+        signers: Vec<Address>,
+        new_signers: Vec<Address>,
+        threshold: u32,
+    ) {
+        crate::admin::require_admin(&e, &signers);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let admin_set = crate::admin::read_admin_set(&e);
+        let new_set = AdminSet {
+            signers: new_signers,
+            threshold,
+        };
+        crate::admin::check_admin_set(&e, &new_set);
+        for signer in new_set.signers.iter() {
+            if !admin_set.signers.contains(&signer) {
+                signer.require_auth();
+            }
+        }
+        crate::admin::write_admin_set(&e, &new_set);
+        crate::admin::remove_pending_admin(&e);
+        e.events().publish(
+            (Symbol::new(&e, "admin_signers_set"), signers),
+            new_set,
+        );
+    }
+
+    /// Changes how many admin signers must authorize admin-only calls.
+    pub fn set_admin_threshold(e: Env, signers: Vec<Address>, threshold: u32) {
+        crate::admin::require_admin(&e, &signers);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let mut admin_set = crate::admin::read_admin_set(&e);
+        admin_set.threshold = threshold;
+        crate::admin::write_admin_set(&e, &admin_set);
+        e.events().publish(
+            (Symbol::new(&e, "admin_signers_set"), signers),
+            admin_set,
+        );
+    }
+
+    pub fn admin(e: Env) -> AdminSet {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::admin::read_admin_set(&e)
+    }
+
+    pub fn pending_admin(e: Env) -> Option<PendingAdmin> {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::admin::read_pending_admin(&e)
+    }
+
+    pub fn freeze_account(
+        e: Env,
+        signers: Vec<Address>,
+        account_to_freeze: Address,
+        duration_ledgers: u32,
+        reason: String,
+        compliance_tag: ComplianceStatus,
+        scope: FreezeScope,
+    ) {
+        let freezer = crate::roles::require_role_or_admin(&e, &signers, Role::Freezer);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let freeze_info = FreezeInfo {
+            start_ledger: e.ledger().sequence(),
+            expiration_ledger: expiration_ledger_after(&e, duration_ledgers),
+            expires_at: 0,
+            reason,
+            compliance_tag,
+            scope,
+        };
+        freeze_one(&e, &freezer, account_to_freeze, freeze_info);
+    }
+
+    /// Freezes `account` until the unix time `expires_at` rather than for a
+    /// number of ledgers, for orders expressed in calendar time.
+    pub fn freeze_account_until(
+        e: Env,
+        signers: Vec<Address>,
+        account: Address,
+        expires_at: u64,
+        reason: String,
+        compliance_tag: ComplianceStatus,
+        scope: FreezeScope,
+    ) {
+        let freezer = crate::roles::require_role_or_admin(&e, &signers, Role::Freezer);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if expires_at <= e.ledger().timestamp() {
+            panic_with_error!(&e, TokenError::InvalidExpirationTime);
+        }
+
+        let freeze_info = FreezeInfo {
+            start_ledger: e.ledger().sequence(),
+            expiration_ledger: 0,
+            expires_at,
+            reason,
+            compliance_tag,
+            scope,
+        };
+        freeze_one(&e, &freezer, account, freeze_info);
+    }
+
+    /// Schedules the freeze window described by `freeze_info`: it applies
+    /// from `start_ledger` until `expiration_ledger` and `expires_at` (0 for
+    /// no bound). Like `freeze_account`, it replaces any existing freeze on
+    /// the account, except that a freeze starting later cannot replace an
+    /// active one, which would lift it until `start_ledger`.
+    pub fn schedule_freeze(
+        e: Env,
+        signers: Vec<Address>,
+        account: Address,
+        freeze_info: FreezeInfo,
+    ) {
+        let freezer = crate::roles::require_role_or_admin(&e, &signers, Role::Freezer);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if freeze_info.start_ledger < e.ledger().sequence() {
+            panic_with_error!(&e, TokenError::InvalidStartLedger);
+        }
+        if freeze_info.expiration_ledger != 0
+            && freeze_info.expiration_ledger <= freeze_info.start_ledger
+        {
+            panic_with_error!(&e, TokenError::InvalidExpirationLedger);
+        }
+        if freeze_info.expires_at != 0 && freeze_info.expires_at <= e.ledger().timestamp() {
+            panic_with_error!(&e, TokenError::InvalidExpirationTime);
+        }
+        if freeze_info.start_ledger > e.ledger().sequence()
+            && is_account_effectively_frozen(&e, &account)
+        {
+            panic_with_error!(&e, TokenError::AccountAlreadyFrozen);
+        }
+
+        freeze_one(&e, &freezer, account, freeze_info);
+    }
+
+    pub fn unfreeze_account(e: Env, signers: Vec<Address>, account_to_unfreeze: Address) {
+        let freezer = crate::roles::require_role_or_admin(&e, &signers, Role::Freezer);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::proposal::require_single_key_allowed(&e);
+
+        unfreeze_one(&e, &freezer, account_to_unfreeze);
+    }
+
+    /// Freezes every `(account, duration_ledgers, reason, compliance_tag)`
+    /// entry with the given scope under a single authorization. At most
+    /// `MAX_FREEZE_BATCH` entries are accepted per call.
+    pub fn freeze_accounts(
+        e: Env,
+        signers: Vec<Address>,
+        freezes: Vec<(Address, u32, String, ComplianceStatus)>,
+        scope: FreezeScope,
+    ) {
+        let freezer = crate::roles::require_role_or_admin(&e, &signers, Role::Freezer);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if freezes.len() > MAX_FREEZE_BATCH {
+            panic_with_error!(&e, TokenError::BatchTooLarge);
+        }
+
+        for (account, duration_ledgers, reason, compliance_tag) in freezes.iter() {
+            let freeze_info = FreezeInfo {
+                start_ledger: e.ledger().sequence(),
+                expiration_ledger: expiration_ledger_after(&e, duration_ledgers),
+                expires_at: 0,
+                reason,
+                compliance_tag,
+                scope,
+            };
+            freeze_one(&e, &freezer, account, freeze_info);
+        }
+    }
+
+    /// Unfreezes every account in `accounts` under a single authorization.
+    /// Accounts that are not frozen are skipped. At most `MAX_FREEZE_BATCH`
+    /// accounts are accepted per call.
+    pub fn unfreeze_accounts(e: Env, signers: Vec<Address>, accounts: Vec<Address>) {
+        let freezer = crate::roles::require_role_or_admin(&e, &signers, Role::Freezer);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::proposal::require_single_key_allowed(&e);
+        if accounts.len() > MAX_FREEZE_BATCH {
+            panic_with_error!(&e, TokenError::BatchTooLarge);
+        }
+
+        for account in accounts.iter() {
+            unfreeze_one(&e, &freezer, account);
+        }
+    }
+
+    /// Changes the expiry, reason and compliance tag of an active freeze
+    /// without lifting it. `new_expiration_ledger` is absolute; 0 makes the
+    /// freeze indefinite. The expiry can be pushed back but not brought
+    /// forward; a freeze is lifted early with `unfreeze_account`.
+    pub fn update_freeze(
+        e: Env,
+        signers: Vec<Address>,
+        account: Address,
+        new_expiration_ledger: u32,
+        new_reason: String,
+        new_tag: ComplianceStatus,
+    ) {
+        let freezer = crate::roles::require_role_or_admin(&e, &signers, Role::Freezer);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let old_info = get_active_freeze_info(&e, &account)
+            .unwrap_or_else(|| panic_with_error!(&e, TokenError::AccountNotFrozen));
+        let brought_forward = old_info.expiration_ledger == 0
+            || new_expiration_ledger < old_info.expiration_ledger;
+        if new_expiration_ledger != 0 && brought_forward {
+            panic_with_error!(&e, TokenError::InvalidExpirationLedger);
+        }
+        let new_info = FreezeInfo {
+            start_ledger: old_info.start_ledger,
+            expiration_ledger: new_expiration_ledger,
+            expires_at: old_info.expires_at,
+            reason: new_reason,
+            compliance_tag: new_tag,
+            scope: old_info.scope,
+        };
+        amend_freeze(&e, freezer, account, old_info, new_info);
+    }
+
+    /// Pushes the ledger expiry of an active timed freeze back by
+    /// `additional_ledgers`. Fails with `NoLedgerExpiry` for freezes without
+    /// one, including timestamp-based ones.
+    pub fn extend_freeze(e: Env, signers: Vec<Address>, account: Address, additional_ledgers: u32) {
+        let freezer = crate::roles::require_role_or_admin(&e, &signers, Role::Freezer);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let old_info = get_active_freeze_info(&e, &account)
+            .unwrap_or_else(|| panic_with_error!(&e, TokenError::AccountNotFrozen));
+        if old_info.expiration_ledger == 0 {
+            panic_with_error!(&e, TokenError::NoLedgerExpiry);
+        }
+        let mut new_info = old_info.clone();
+        new_info.expiration_ledger = old_info
+            .expiration_ledger
+            .checked_add(additional_ledgers)
+            .unwrap_or_else(|| panic_with_error!(&e, TokenError::DurationOverflow));
+        amend_freeze(&e, freezer, account, old_info, new_info);
+    }
+
+    pub fn freeze_amount(
+        e: Env,
+        signers: Vec<Address>,
+        account: Address,
+        amount: i128,
+        duration_ledgers: u32,
+        reason: String,
+        compliance_tag: ComplianceStatus,
+    ) -> u32 {
+        let freezer = crate::roles::require_role_or_admin(&e, &signers, Role::Freezer);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if amount <= 0 {
+            panic_with_error!(&e, TokenError::InvalidLockAmount);
+        }
+
+        let expiration_ledger = expiration_ledger_after(&e, duration_ledgers);
+        let lock_id = crate::lock::add_lock(
+            &e,
+            account.clone(),
+            AmountLock {
+                id: 0,
+                amount,
+                expiration_ledger,
+                reason: reason.clone(),
+                compliance_tag,
+            },
+        );
+        e.events().publish(
+            (Symbol::new(&e, "freeze_amt"), freezer, account),
+            (lock_id, amount, expiration_ledger, reason, compliance_tag),
+        );
+        lock_id
+    }
+
+    pub fn release_amount(e: Env, signers: Vec<Address>, account: Address, lock_id: u32) {
+        let freezer = crate::roles::require_role_or_admin(&e, &signers, Role::Freezer);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::proposal::require_single_key_allowed(&e);
+        release_lock(&e, freezer, account, lock_id);
+    }
+
+    /// Retags an active freeze. The change is recorded like `update_freeze`,
+    /// with a history entry and a `freeze_amended` event.
+    pub fn set_compliance_tag(
+        e: Env,
+        signers: Vec<Address>,
+        account: Address,
+        compliance_tag: ComplianceStatus,
+    ) {
+        let officer = crate::roles::require_role_or_admin(&e, &signers, Role::ComplianceOfficer);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let old_info = get_active_freeze_info(&e, &account)
+            .unwrap_or_else(|| panic_with_error!(&e, TokenError::AccountNotFrozen));
+        let mut new_info = old_info.clone();
+        new_info.compliance_tag = compliance_tag;
+        amend_freeze(&e, officer, account, old_info, new_info);
+    }
+
+    /// Records `status` for `account` in the compliance registry, independent
+    /// of any freeze. `evidence_hash` identifies the supporting documents.
+    pub fn set_compliance_status(
+        e: Env,
+        officer: Address,
+        account: Address,
+        status: ComplianceStatus,
+        evidence_hash: BytesN<32>,
+    ) {
+        crate::roles::require_role(&e, &officer, Role::ComplianceOfficer);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+
+        let old_status = crate::compliance::read_compliance_status(&e, account.clone());
+        let record = ComplianceRecord {
+            status,
+            evidence_hash: evidence_hash.clone(),
+            set_by: officer.clone(),
+            set_at_ledger: e.ledger().sequence(),
+        };
+        crate::compliance::write_compliance_record(&e, account.clone(), record);
+        e.events().publish(
+            (Symbol::new(&e, "compliance_status"), account),
+            (officer, old_status, status, evidence_hash),
+        );
+    }
+
+    pub fn get_compliance_status(e: Env, account: Address) -> ComplianceStatus {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::compliance::read_compliance_status(&e, account)
+    }
+
+    pub fn get_compliance_history(e: Env, account: Address) -> Vec<ComplianceRecord> {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::compliance::read_compliance_history(&e, account)
+    }
+
+    /// Sets the operations allowed for accounts registered with `status`.
+    pub fn set_compliance_policy(
+        e: Env,
+        signers: Vec<Address>,
+        status: ComplianceStatus,
+        policy: CompliancePolicy,
+    ) {
+        crate::admin::require_admin(&e, &signers);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if policy.send_limit.is_some_and(|limit| limit < 0) {
+            panic_with_error!(&e, TokenError::NegativeAmount);
+        }
+        crate::compliance::write_policy(&e, status, &policy);
+        e.events().publish(
+            (Symbol::new(&e, "compliance_policy"), signers, status),
+            policy,
+        );
+    }
+
+    pub fn get_compliance_policy(e: Env, status: ComplianceStatus) -> CompliancePolicy {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::compliance::read_policy(&e, status)
+    }
+
+    /// Approves `accounts` to hold the token until `expiration_ledger`
+    /// (0 for no expiry), replacing any earlier approval. At most
+    /// `MAX_ALLOWLIST_BATCH` accounts are accepted per call.
+    pub fn add_to_allowlist(
+        e: Env,
+        officer: Address,
+        accounts: Vec<Address>,
+        expiration_ledger: u32,
+    ) {
+        crate::roles::require_role(&e, &officer, Role::ComplianceOfficer);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if accounts.len() > MAX_ALLOWLIST_BATCH {
+            panic_with_error!(&e, TokenError::BatchTooLarge);
+        }
+        if expiration_ledger != 0 && expiration_ledger <= e.ledger().sequence() {
+            panic_with_error!(&e, TokenError::InvalidExpirationLedger);
+        }
+
+        for account in accounts.iter() {
+            crate::allowlist::write_allowlist_entry(&e, account.clone(), expiration_ledger);
+            e.events().publish(
+                (Symbol::new(&e, "allowlist_added"), account),
+                (officer.clone(), expiration_ledger),
+            );
+        }
+    }
+
+    /// Removes `accounts` from the allowlist. Accounts that are not listed are
+    /// skipped. At most `MAX_ALLOWLIST_BATCH` accounts are accepted per call.
+    pub fn remove_from_allowlist(e: Env, officer: Address, accounts: Vec<Address>) {
+        crate::roles::require_role(&e, &officer, Role::ComplianceOfficer);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if accounts.len() > MAX_ALLOWLIST_BATCH {
+            panic_with_error!(&e, TokenError::BatchTooLarge);
+        }
+
+        for account in accounts.iter() {
+            if crate::allowlist::remove_allowlist_entry(&e, account.clone()) {
+                e.events().publish(
+                    (Symbol::new(&e, "allowlist_removed"), account),
+                    officer.clone(),
+                );
+            }
+        }
+    }
+
+    /// Sets or clears the external contract consulted through `can_transfer`
+    /// before every mint, transfer and burn.
+    pub fn set_compliance_contract(
+        e: Env,
+        signers: Vec<Address>,
+        compliance_contract: Option<Address>,
+    ) {
+        crate::admin::require_admin(&e, &signers);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::compliance_hook::write_compliance_contract(&e, &compliance_contract);
+        e.events().publish(
+            (Symbol::new(&e, "compliance_contract"), signers),
+            compliance_contract,
+        );
+    }
+
+    pub fn compliance_contract(e: Env) -> Option<Address> {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::compliance_hook::read_compliance_contract(&e)
+    }
+
+    /// Sets or clears the outflow limit applied to accounts without their own.
+    pub fn set_default_velocity_limit(e: Env, signers: Vec<Address>, limit: Option<VelocityLimit>) {
+        crate::admin::require_admin(&e, &signers);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        check_velocity_limit(&e, &limit);
+        crate::velocity::write_default_limit(&e, &limit);
+        e.events().publish(
+            (Symbol::new(&e, "velocity_default"), signers),
+            limit,
+        );
+    }
+
+    /// Sets or clears the outflow limit of `account`, which takes precedence
+    /// over the default limit.
+    pub fn set_velocity_limit(
+        e: Env,
+        signers: Vec<Address>,
+        account: Address,
+        limit: Option<VelocityLimit>,
+    ) {
+        crate::admin::require_admin(&e, &signers);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        check_velocity_limit(&e, &limit);
+        crate::velocity::write_account_limit(&e, account.clone(), &limit);
+        e.events().publish(
+            (Symbol::new(&e, "velocity_limit"), signers, account),
+            limit,
+        );
     }
-}
 
-fn get_active_freeze_info(e: &Env, account: &Address) -> Option<FreezeInfo> {
-    let key = DataKey::AccountFreezeInfo(account.clone());
-    if let Some(info) = e.storage().instance().get::<_, FreezeInfo>(&key) {
-        if info.expiration_ledger == 0 {
-            // Süresiz dondurma, her zaman aktif
-            return Some(info);
-        } else {
-            // Süreli dondurma
-            if e.ledger().sequence() < info.expiration_ledger {
-                return Some(info);
-            } else {
-                return None;
-            }
+    /// The limit that applies to `account`, if any.
+    pub fn velocity_limit(e: Env, account: Address) -> Option<VelocityLimit> {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::velocity::effective_limit(&e, account)
+    }
+
+    /// How much `account` can still send or burn in the current window, or
+    /// `None` if no limit applies.
+    pub fn remaining_limit(e: Env, account: Address) -> Option<i128> {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::velocity::remaining_limit(&e, account)
+    }
+
+    /// The outflow counted against the limit of `account` and when it starts
+    /// to free up, or `None` if no limit applies. A transfer blocked by the
+    /// limit only fails with `VelocityLimitExceeded`, so clients check this
+    /// beforehand.
+    pub fn velocity_usage(e: Env, account: Address) -> Option<VelocityUsage> {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::velocity::read_usage(&e, account)
+    }
+
+    /// Adds an escalation rule evaluated after every `transfer` and
+    /// `transfer_from`, and returns its id.
+    pub fn add_escalation_rule(
+        e: Env,
+        signers: Vec<Address>,
+        trigger: EscalationTrigger,
+        compliance_tag: ComplianceStatus,
+        scope: FreezeScope,
+        duration_ledgers: u32,
+        reason: String,
+    ) -> u32 {
+        crate::admin::require_admin(&e, &signers);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if let EscalationTrigger::SendAbove(_, threshold) = trigger {
+            check_nonnegative_amount(&e, threshold);
         }
+        let rule = EscalationRule {
+            id: 0,
+            trigger,
+            compliance_tag,
+            scope,
+            duration_ledgers,
+            reason,
+        };
+        let id = crate::escalation::add_rule(&e, rule.clone());
+        e.events().publish(
+            (Symbol::new(&e, "rule_added"), signers, id),
+            EscalationRule { id, ..rule },
+        );
+        id
     }
-    None
-}
 
-fn is_account_effectively_frozen(e: &Env, account: &Address) -> bool {
-    get_active_freeze_info(e, account).is_some()
-}
+    pub fn remove_escalation_rule(e: Env, signers: Vec<Address>, rule_id: u32) {
+        crate::admin::require_admin(&e, &signers);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::escalation::remove_rule(&e, rule_id);
+        e.events().publish((Symbol::new(&e, "rule_removed"), signers, rule_id), ());
+    }
 
-fn emit_freeze_event(
-    e: &Env,
-    event_type_symbol: Symbol,
-    admin: Address,
-    account: Address,
-    freeze_info: Option<FreezeInfo>,
-) {
-    let mut topics: Vec<Val> = Vec::new(e);
-    topics.push_back(event_type_symbol.into_val(e));
-    topics.push_back(symbol_short!("admin").into_val(e));
-    topics.push_back(symbol_short!("account").into_val(e));
+    pub fn get_escalation_rules(e: Env) -> Vec<EscalationRule> {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::escalation::read_rules(&e)
+    }
 
-    let mut data: Vec<Val> = Vec::new(e);
-    data.push_back(admin.into_val(e));
-    data.push_back(account.into_val(e));
+    /// Whether `account` may currently hold the token. Always true when the
+    /// token was not initialized in allowlist mode.
+    pub fn is_allowed(e: Env, account: Address) -> bool {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::allowlist::is_allowed(&e, &account)
+    }
 
-    if let Some(info) = freeze_info {
-        if info.expiration_ledger != 0 {
-            topics.push_back(symbol_short!("expires").into_val(e));
-            data.push_back(info.expiration_ledger.into_val(e));
-        }
-        topics.push_back(symbol_short!("reason").into_val(e));
-        data.push_back(info.reason.clone().into_val(e));
+    pub fn allowlist_mode(e: Env) -> bool {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::allowlist::is_allowlist_mode(&e)
+    }
 
-        if info.compliance_tag != ComplianceStatus::None {
-            topics.push_back(Symbol::new(e, "compliance").into_val(e));
-            data.push_back(info.compliance_tag.into_val(e));
-        }
+    pub fn grant_role(e: Env, caller: Address, role: Role, account: Address) {
+        crate::roles::require_role(&e, &caller, Role::AccessManager);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::roles::grant_role(&e, role, account.clone());
+        e.events().publish(
+            (Symbol::new(&e, "role_granted"), role, account),
+            caller,
+        );
     }
-    e.events().publish(topics, data);
-}
 
-#[contract]
-pub struct Token;
+    pub fn revoke_role(e: Env, caller: Address, role: Role, account: Address) {
+        crate::roles::require_role(&e, &caller, Role::AccessManager);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::roles::revoke_role(&e, role, account.clone());
+        e.events().publish(
+            (Symbol::new(&e, "role_revoked"), role, account),
+            caller,
+        );
+    }
 
-#[contractimpl]
-impl Token {
-    pub fn initialize(e: Env, admin: Address, decimal: u32, name: String, symbol: String) {
-        if crate::admin::has_administrator(&e) {
-            panic!("already initialized");
+    /// Grants a role on the authority of the admin set. This is how a set
+    /// with a threshold above 1, whose signers hold no role on their own,
+    /// delegates role-gated operations.
+    pub fn admin_grant_role(e: Env, signers: Vec<Address>, role: Role, account: Address) {
+        crate::admin::require_admin(&e, &signers);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::roles::grant_role(&e, role, account.clone());
+        e.events().publish(
+            (Symbol::new(&e, "role_granted"), role, account),
+            signers,
+        );
+    }
+
+    pub fn admin_revoke_role(e: Env, signers: Vec<Address>, role: Role, account: Address) {
+        crate::admin::require_admin(&e, &signers);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::roles::revoke_role(&e, role, account.clone());
+        e.events().publish(
+            (Symbol::new(&e, "role_revoked"), role, account),
+            signers,
+        );
+    }
+
+    pub fn renounce_role(e: Env, account: Address, role: Role) {
+        account.require_auth();
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::roles::revoke_role(&e, role, account.clone());
+        e.events().publish(
+            (Symbol::new(&e, "role_renounced"), role, account),
+            Val::VOID,
+        );
+    }
+
+    pub fn has_role(e: Env, role: Role, account: Address) -> bool {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::roles::has_role(&e, role, &account)
+    }
+
+    pub fn get_role_members(e: Env, role: Role) -> Vec<Address> {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::roles::read_role_members(&e, role)
+    }
+
+    /// One-time configuration of the supply cap. The cap cannot be removed
+    /// afterwards, only adjusted through `update_max_supply`.
+    pub fn set_max_supply(e: Env, signers: Vec<Address>, max_supply: i128, raisable: bool) {
+        crate::admin::require_admin(&e, &signers);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        if crate::supply::read_supply_cap(&e).is_some() {
+            panic_with_error!(&e, TokenError::SupplyCapAlreadySet);
         }
-        crate::admin::write_administrator(&e, &admin);
-        if decimal > u8::MAX.into() {
-            panic!("Decimal must fit in a u8");
+        if max_supply < crate::supply::read_total_supply(&e) {
+            panic_with_error!(&e, TokenError::InvalidSupplyCap);
         }
-        crate::metadata::write_metadata(
-            &e,
-            soroban_token_sdk::metadata::TokenMetadata { decimal, name, symbol },
+        crate::supply::write_supply_cap(&e, &SupplyCap { max_supply, raisable });
+        e.events().publish(
+            (Symbol::new(&e, "max_supply_set"), signers),
+            (max_supply, raisable),
+        );
+    }
+
+    pub fn update_max_supply(e: Env, signers: Vec<Address>, new_max_supply: i128) {
+        crate::admin::require_admin(&e, &signers);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let mut cap = crate::supply::read_supply_cap(&e)
+            .unwrap_or_else(|| panic_with_error!(&e, TokenError::SupplyCapNotSet));
+        if new_max_supply > cap.max_supply && !cap.raisable {
+            panic_with_error!(&e, TokenError::SupplyCapIncreaseNotAllowed);
+        }
+        if new_max_supply < crate::supply::read_total_supply(&e) {
+            panic_with_error!(&e, TokenError::InvalidSupplyCap);
+        }
+        let old_max_supply = cap.max_supply;
+        cap.max_supply = new_max_supply;
+        crate::supply::write_supply_cap(&e, &cap);
+        e.events().publish(
+            (Symbol::new(&e, "max_supply_updated"), signers),
+            (old_max_supply, new_max_supply),
         );
+    }
+
+    pub fn max_supply(e: Env) -> Option<i128> {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::supply::read_supply_cap(&e).map(|cap| cap.max_supply)
+    }
+
+    /// Burns `amount` from `from` regardless of freezes or amount locks.
+    pub fn clawback(e: Env, signers: Vec<Address>, from: Address, amount: i128, reason: String) {
+        check_nonnegative_amount(&e, amount);
+        crate::admin::require_admin(&e, &signers);
         e.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::proposal::require_single_key_allowed(&e);
+
+        clawback_from(&e, signers, from, amount, reason);
     }
 
-    pub fn mint(e: Env, to: Address, amount: i128) {
-        check_nonnegative_amount(amount);
-        let admin = crate::admin::read_administrator(&e);
-        admin.require_auth();
+    /// Moves `amount` from `from` to a custody address regardless of freezes
+    /// or amount locks on `from`. The custody address must be able to receive
+    /// like any recipient, including being allowlisted in allowlist mode.
+    pub fn forced_transfer(
+        e: Env,
+        signers: Vec<Address>,
+        from: Address,
+        to: Address,
+        amount: i128,
+        reason: String,
+    ) {
+        check_nonnegative_amount(&e, amount);
+        crate::admin::require_admin(&e, &signers);
         e.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::proposal::require_single_key_allowed(&e);
+        require_can_receive(&e, &to);
+        crate::allowlist::require_recipient_allowed(&e, &to);
+
+        crate::balance::seize_balance(&e, from.clone(), amount);
         crate::balance::receive_balance(&e, to.clone(), amount);
+        let compliance_tag = active_compliance_tag(&e, &from);
         e.events().publish(
-            (Symbol::new(&e, "mint"), admin.clone(), to.clone()),
-            amount,
+            (Symbol::new(&e, "forced_transfer"), signers, from, to),
+            (amount, reason, compliance_tag),
+        );
+    }
+
+    pub fn pause(e: Env, guardian: Address, reason: String, duration_ledgers: u32) {
+        crate::roles::require_role(&e, &guardian, Role::Guardian);
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let expiration_ledger = expiration_ledger_after(&e, duration_ledgers);
+        crate::pause::write_pause_info(
+            &e,
+            &PauseInfo {
+                paused_by: guardian.clone(),
+                reason: reason.clone(),
+                expiration_ledger,
+            },
+        );
+        e.events().publish(
+            (Symbol::new(&e, "paused"), guardian),
+            (reason, expiration_ledger),
         );
     }
 
-    pub fn set_admin(e: Env, new_admin: Address) {
-        let admin = crate::admin::read_administrator(&e);
-        admin.require_auth();
+    pub fn unpause(e: Env, guardian: Address) {
+        crate::roles::require_role(&e, &guardian, Role::Guardian);
         e.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        crate::admin::write_administrator(&e, &new_admin.clone());
+        if crate::pause::get_active_pause_info(&e).is_none() {
+            panic_with_error!(&e, TokenError::NotPaused);
+        }
+        crate::pause::remove_pause_info(&e);
         e.events().publish(
-            (Symbol::new(&e, "set_admin"), admin, new_admin),
+            (Symbol::new(&e, "unpaused"), guardian),
             Val::VOID,
         );
     }
 
-    pub fn freeze_account(
-        e: Env,
-        account_to_freeze: Address,
-        duration_ledgers: u32,
-        reason: String,
-        compliance_tag: ComplianceStatus,
-    ) {
-        let admin = crate::admin::read_administrator(&e);
-        admin.require_auth();
+    pub fn is_paused(e: Env) -> bool {
         e.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::pause::get_active_pause_info(&e).is_some()
+    }
 
-        let expiration_ledger = if duration_ledgers == 0 {
-            0
-        } else {
-            e.ledger()
-                .sequence()
-                .checked_add(duration_ledgers)
-                .expect("Ledger overflow for freeze duration")
-        };
+    pub fn get_pause_info(e: Env) -> Option<PauseInfo> {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::pause::get_active_pause_info(&e)
+    }
 
-        let freeze_info_to_store = FreezeInfo {
-            expiration_ledger,
-            reason: reason.clone(),
-            compliance_tag,
-        };
+    pub fn total_supply(e: Env) -> i128 {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::supply::read_total_supply(&e)
+    }
+
+    pub fn get_amount_locks(e: Env, account: Address) -> Vec<AmountLock> {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::lock::read_active_locks(&e, account)
+    }
 
-        let key = DataKey::AccountFreezeInfo(account_to_freeze.clone());
-        e.storage().instance().set(&key, &freeze_info_to_store);
-        
+    pub fn locked_balance(e: Env, account: Address) -> i128 {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::lock::read_locked_amount(&e, account)
+    }
 
-        emit_freeze_event(
-            &e,
-            Symbol::new(&e, "freeze_acc"),
-            admin,
-            account_to_freeze,
-            Some(freeze_info_to_store),
-        );
+    pub fn spendable_balance(e: Env, account: Address) -> i128 {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        let balance = crate::balance::read_balance(&e, account.clone());
+        let locked = crate::lock::read_locked_amount(&e, account);
+        if balance > locked {
+            balance - locked
+        } else {
+            0
+        }
     }
 
-    pub fn unfreeze_account(e: Env, account_to_unfreeze: Address) {
-        let admin = crate::admin::read_administrator(&e);
-        admin.require_auth();
+    pub fn migrate_freeze_records(e: Env, signers: Vec<Address>, accounts: Vec<Address>) -> u32 {
+        crate::admin::require_admin(&e, &signers);
         e.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        let key = DataKey::AccountFreezeInfo(account_to_unfreeze.clone());
 
-        if e.storage().instance().has(&key) {
-            e.storage().instance().remove(&key);
-            emit_freeze_event(
-                &e,
-                Symbol::new(&e, "unfrz_acc"),
-                admin,
-                account_to_unfreeze,
-                None,
-            );
+        let mut migrated: u32 = 0;
+        for account in accounts.iter() {
+            if crate::freeze::migrate_legacy_freeze_info(&e, account) {
+                migrated += 1;
+            }
         }
+        e.events().publish(
+            (Symbol::new(&e, "migrate_frz"), signers),
+            migrated,
+        );
+        migrated
+    }
+
+    pub fn get_freeze_history(
+        e: Env,
+        account: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<FreezeHistoryEntry> {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::history::read_history(&e, account, start, limit)
+    }
+
+    pub fn get_freeze_history_len(e: Env, account: Address) -> u32 {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::history::read_history_len(&e, account)
+    }
+
+    pub fn list_frozen_accounts(
+        e: Env,
+        cursor: u32,
+        limit: u32,
+        compliance_tag: Option<ComplianceStatus>,
+    ) -> FrozenAccountsPage {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::frozen_index::read_frozen_accounts(&e, cursor, limit, compliance_tag, false)
+    }
+
+    /// Removes lapsed timed freezes from the frozen account index, which
+    /// keeps them until then. Anyone may call it; scan from cursor 0 and
+    /// repeat with the returned cursor until it is `None`.
+    pub fn prune_frozen_index(e: Env, cursor: u32, limit: u32) -> Option<u32> {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::frozen_index::prune_lapsed(&e, cursor, limit)
+    }
+
+    /// Like `list_frozen_accounts`, but returns scheduled freezes that have
+    /// not started yet.
+    pub fn list_pending_freezes(
+        e: Env,
+        cursor: u32,
+        limit: u32,
+        compliance_tag: Option<ComplianceStatus>,
+    ) -> FrozenAccountsPage {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::frozen_index::read_frozen_accounts(&e, cursor, limit, compliance_tag, true)
     }
 
     pub fn get_account_freeze_details(e: Env, account: Address) -> Option<FreezeInfo> {
@@ -185,6 +1508,13 @@ impl Token {
         get_active_freeze_info(&e, &account)
     }
 
+    pub fn get_pending_freeze(e: Env, account: Address) -> Option<FreezeInfo> {
+        e.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+        crate::freeze::get_pending_freeze_info(&e, &account)
+    }
+
     pub fn is_frozen(e: Env, account: Address) -> bool {
         e.storage()
             .instance()
@@ -196,11 +1526,7 @@ impl Token {
         e.storage()
             .instance()
             .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        if let Some(info) = get_active_freeze_info(&e, &account) {
-            info.compliance_tag
-        } else {
-            ComplianceStatus::None
-        }
+        active_compliance_tag(&e, &account)
     }
 }
 
@@ -213,10 +1539,11 @@ impl StandardTokenInterface for Token {
 
     fn approve(e: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32) {
         from.require_auth();
-        check_nonnegative_amount(amount);
+        check_nonnegative_amount(&e, amount);
         e.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        if is_account_effectively_frozen(&e, &from) {
-            panic!("Hesap dondurulmuş ve approve işlemi yapılamaz");
+        crate::pause::require_not_paused(&e);
+        if is_outbound_frozen(&e, &from) {
+            panic_with_error!(&e, TokenError::AccountFrozen);
         }
         crate::allowance::write_allowance(&e, from.clone(), spender.clone(), amount, expiration_ledger);
         e.events().publish(
@@ -232,43 +1559,58 @@ impl StandardTokenInterface for Token {
 
     fn transfer(e: Env, from: Address, to: Address, amount: i128) {
         from.require_auth();
-        check_nonnegative_amount(amount);
+        check_nonnegative_amount(&e, amount);
         e.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        if is_account_effectively_frozen(&e, &from) {
-            panic!("Hesap dondurulmuş ve token transfer edilemez");
-        }
+        crate::pause::require_not_paused(&e);
+        require_can_send(&e, &from, amount);
+        require_can_receive(&e, &to);
+        crate::allowlist::require_sender_allowed(&e, &from);
+        crate::allowlist::require_recipient_allowed(&e, &to);
+        crate::compliance_hook::require_approved(&e, Some(from.clone()), Some(to.clone()), amount);
+        crate::velocity::record_outflow(&e, from.clone(), amount);
         crate::balance::spend_balance(&e, from.clone(), amount);
         crate::balance::receive_balance(&e, to.clone(), amount);
+        crate::compliance_hook::notify_transferred(&e, Some(from.clone()), Some(to.clone()), amount);
         e.events().publish(
-            (Symbol::new(&e, "transfer"), from, to),
+            (Symbol::new(&e, "transfer"), from.clone(), to.clone()),
             amount,
         );
+        apply_escalation_rules(&e, &from, &to, amount);
     }
 
     fn transfer_from(e: Env, spender: Address, from: Address, to: Address, amount: i128) {
         spender.require_auth();
-        check_nonnegative_amount(amount);
+        check_nonnegative_amount(&e, amount);
         e.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        if is_account_effectively_frozen(&e, &from) {
-            panic!("Hesap (from) dondurulmuş ve token transfer edilemez");
-        }
+        crate::pause::require_not_paused(&e);
+        require_can_send(&e, &from, amount);
+        require_can_receive(&e, &to);
+        crate::allowlist::require_sender_allowed(&e, &from);
+        crate::allowlist::require_recipient_allowed(&e, &to);
+        crate::compliance_hook::require_approved(&e, Some(from.clone()), Some(to.clone()), amount);
         crate::allowance::spend_allowance(&e, from.clone(), spender.clone(), amount);
+        crate::velocity::record_outflow(&e, from.clone(), amount);
         crate::balance::spend_balance(&e, from.clone(), amount);
         crate::balance::receive_balance(&e, to.clone(), amount);
+        crate::compliance_hook::notify_transferred(&e, Some(from.clone()), Some(to.clone()), amount);
         e.events().publish(
-            (Symbol::new(&e, "transfer"), from, to),
+            (Symbol::new(&e, "transfer"), from.clone(), to.clone()),
             amount,
         );
+        apply_escalation_rules(&e, &from, &to, amount);
     }
 
     fn burn(e: Env, from: Address, amount: i128) {
         from.require_auth();
-        check_nonnegative_amount(amount);
+        check_nonnegative_amount(&e, amount);
         e.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        if is_account_effectively_frozen(&e, &from) {
-            panic!("Hesap dondurulmuş ve token yakılamaz");
-        }
+        crate::pause::require_not_paused(&e);
+        require_can_send(&e, &from, amount);
+        crate::compliance_hook::require_approved(&e, Some(from.clone()), None, amount);
+        crate::velocity::record_outflow(&e, from.clone(), amount);
         crate::balance::spend_balance(&e, from.clone(), amount);
+        crate::supply::decrease_supply(&e, amount);
+        crate::compliance_hook::notify_transferred(&e, Some(from.clone()), None, amount);
         e.events().publish(
             (Symbol::new(&e, "burn"), from),
             amount,
@@ -277,13 +1619,16 @@ impl StandardTokenInterface for Token {
 
     fn burn_from(e: Env, spender: Address, from: Address, amount: i128) {
         spender.require_auth();
-        check_nonnegative_amount(amount);
+        check_nonnegative_amount(&e, amount);
         e.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
-        if is_account_effectively_frozen(&e, &from) {
-            panic!("Hesap (from) dondurulmuş ve token yakılamaz");
-        }
+        crate::pause::require_not_paused(&e);
+        require_can_send(&e, &from, amount);
+        crate::compliance_hook::require_approved(&e, Some(from.clone()), None, amount);
         crate::allowance::spend_allowance(&e, from.clone(), spender, amount);
+        crate::velocity::record_outflow(&e, from.clone(), amount);
         crate::balance::spend_balance(&e, from.clone(), amount);
+        crate::supply::decrease_supply(&e, amount);
+        crate::compliance_hook::notify_transferred(&e, Some(from.clone()), None, amount);
         e.events().publish(
             (Symbol::new(&e, "burn"), from),
             amount,
@@ -0,0 +1,80 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TokenError {
+    NotInitialized = 1,
+    AlreadyInitialized = 2,
+    NegativeAmount = 3,
+    InsufficientBalance = 4,
+    InsufficientAllowance = 5,
+    AccountFrozen = 6,
+    RecipientFrozen = 7,
+    DecimalTooLarge = 8,
+    DurationOverflow = 9,
+    InvalidExpirationLedger = 10,
+    BalanceLocked = 11,
+    TooManyLocks = 12,
+    LockNotFound = 13,
+    InvalidLockAmount = 14,
+    MissingRole = 15,
+    RoleAlreadyGranted = 16,
+    RoleNotGranted = 17,
+    TooManyRoleMembers = 18,
+    AccountNotFrozen = 19,
+    NoPendingAdmin = 20,
+    AdminDelayNotElapsed = 21,
+    InvalidDelay = 22,
+    SupplyOverflow = 23,
+    MaxSupplyExceeded = 24,
+    SupplyCapAlreadySet = 25,
+    SupplyCapNotSet = 26,
+    InvalidSupplyCap = 27,
+    SupplyCapIncreaseNotAllowed = 28,
+    ContractPaused = 29,
+    NotPaused = 30,
+    BatchTooLarge = 31,
+    InvalidStartLedger = 32,
+    InvalidExpirationTime = 33,
+    SenderNotCompliant = 34,
+    RecipientNotCompliant = 35,
+    ComplianceLimitExceeded = 36,
+    SenderNotAllowed = 37,
+    RecipientNotAllowed = 38,
+    ComplianceContractDenied = 39,
+    InvalidVelocityWindow = 40,
+    VelocityLimitExceeded = 41,
+    TooManyEscalationRules = 42,
+    EscalationRuleNotFound = 43,
+    NotAMinter = 44,
+    MinterExpired = 45,
+    MinterQuotaExceeded = 46,
+    // 47 to 58 are taken by `GovernanceError`.
+    LegacySupplyRequired = 59,
+    AccountAlreadyFrozen = 60,
+    ReasonTooLong = 61,
+    NoLedgerExpiry = 62,
+}
+
+/// Errors raised by the dual control and admin governance entry points.
+/// A contract error enum is limited to 50 cases, so these live apart from
+/// `TokenError` and continue its numbering, skipping the codes it already uses.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum GovernanceError {
+    DualControlRequired = 47,
+    DualControlDisabled = 48,
+    InvalidDualControlConfig = 49,
+    ProposalNotFound = 50,
+    ProposalNotPending = 51,
+    ProposalExpired = 52,
+    ProposerCannotApprove = 53,
+    AlreadyApproved = 54,
+    InvalidAdminSet = 55,
+    NotAnAdminSigner = 56,
+    DuplicateAdminSigner = 57,
+    InsufficientAdminSigners = 58,
+    AdminTransferRequiresSingleSigner = 63,
+}
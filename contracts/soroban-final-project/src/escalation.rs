@@ -0,0 +1,99 @@
+use crate::error::TokenError;
+use crate::storage_types::{
+    ComplianceStatus, DataKey, EscalationRule, EscalationTrigger, MAX_ESCALATION_RULES,
+    MAX_RULE_REASON_LEN,
+};
+use soroban_sdk::{panic_with_error, Address, Env, String, Vec};
+
+pub fn read_rules(e: &Env) -> Vec<EscalationRule> {
+    e.storage()
+        .instance()
+        .get::<DataKey, Vec<EscalationRule>>(&DataKey::EscalationRules)
+        .unwrap_or_else(|| Vec::new(e))
+}
+
+fn write_rules(e: &Env, rules: &Vec<EscalationRule>) {
+    if rules.is_empty() {
+        e.storage().instance().remove(&DataKey::EscalationRules);
+    } else {
+        e.storage().instance().set(&DataKey::EscalationRules, rules);
+    }
+}
+
+/// Adds `rule` under a fresh id and returns the id. Ids are never reused.
+pub fn add_rule(e: &Env, mut rule: EscalationRule) -> u32 {
+    let mut rules = read_rules(e);
+    if rules.len() >= MAX_ESCALATION_RULES {
+        panic_with_error!(e, TokenError::TooManyEscalationRules);
+    }
+    if rule.reason.len() > MAX_RULE_REASON_LEN {
+        panic_with_error!(e, TokenError::ReasonTooLong);
+    }
+    let id = e
+        .storage()
+        .instance()
+        .get::<DataKey, u32>(&DataKey::NextEscalationRuleId)
+        .unwrap_or(1);
+    rule.id = id;
+    rules.push_back(rule);
+    write_rules(e, &rules);
+    e.storage()
+        .instance()
+        .set(&DataKey::NextEscalationRuleId, &(id + 1));
+    id
+}
+
+pub fn remove_rule(e: &Env, id: u32) {
+    let mut rules = read_rules(e);
+    let index = rules
+        .iter()
+        .position(|rule| rule.id == id)
+        .unwrap_or_else(|| panic_with_error!(e, TokenError::EscalationRuleNotFound));
+    rules.remove(index as u32);
+    write_rules(e, &rules);
+}
+
+/// Returns the account `rule` should freeze after `amount` moved from `from`
+/// (tagged `from_tag`) to `to`, if the rule fires.
+pub fn rule_target(
+    rule: &EscalationRule,
+    from: &Address,
+    from_tag: ComplianceStatus,
+    to: &Address,
+    amount: i128,
+) -> Option<Address> {
+    match rule.trigger {
+        EscalationTrigger::SendAbove(tag, threshold) if from_tag == tag && amount > threshold => {
+            Some(from.clone())
+        }
+        EscalationTrigger::ReceiveFrom(tag) if from_tag == tag => Some(to.clone()),
+        _ => None,
+    }
+}
+
+/// Prefix prepended to the rule's reason on an automatic freeze, at most
+/// `rule 4294967295: `.
+const MAX_REASON_PREFIX: usize = 17;
+
+/// The reason recorded on a freeze applied by `rule`: the rule's reason
+/// prefixed with its id, e.g. `rule 3: large transfer`, so the record still
+/// names the rule once the `auto_freeze` event is gone.
+pub fn freeze_reason(e: &Env, rule: &EscalationRule) -> String {
+    let mut buf = [0u8; MAX_REASON_PREFIX + MAX_RULE_REASON_LEN as usize];
+    buf[..5].copy_from_slice(b"rule ");
+    let mut len = 5;
+    let mut divisor = 1;
+    while rule.id / divisor >= 10 {
+        divisor *= 10;
+    }
+    while divisor > 0 {
+        buf[len] = b'0' + (rule.id / divisor % 10) as u8;
+        len += 1;
+        divisor /= 10;
+    }
+    buf[len..len + 2].copy_from_slice(b": ");
+    len += 2;
+    let end = len + rule.reason.len() as usize;
+    rule.reason.copy_into_slice(&mut buf[len..end]);
+    String::from_bytes(e, &buf[..end])
+}
@@ -0,0 +1,148 @@
+use crate::storage_types::{
+    DataKey, FreezeInfo, FreezeScope, LegacyFreezeInfo, FREEZE_BUMP_AMOUNT,
+    FREEZE_LIFETIME_THRESHOLD,
+};
+use soroban_sdk::{Address, Env};
+
+// Freeze records used to live in instance storage. Reads fall back to that
+// location until `migrate_legacy_freeze_info` has moved the record.
+fn read_legacy_freeze_info(e: &Env, addr: Address) -> Option<FreezeInfo> {
+    let key = DataKey::AccountFreezeInfo(addr);
+    e.storage()
+        .instance()
+        .get::<DataKey, LegacyFreezeInfo>(&key)
+        .map(|legacy| FreezeInfo {
+            start_ledger: 0,
+            expiration_ledger: legacy.expiration_ledger,
+            expires_at: 0,
+            reason: legacy.reason,
+            compliance_tag: legacy.compliance_tag,
+            scope: FreezeScope::Outbound,
+        })
+}
+
+pub fn read_freeze_info(e: &Env, addr: Address) -> Option<FreezeInfo> {
+    let key = DataKey::AccountFreezeInfo(addr.clone());
+    if let Some(info) = e.storage().persistent().get::<DataKey, FreezeInfo>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, FREEZE_LIFETIME_THRESHOLD, FREEZE_BUMP_AMOUNT);
+        crate::frozen_index::extend_ttl(e, addr);
+        Some(info)
+    } else {
+        read_legacy_freeze_info(e, addr)
+    }
+}
+
+pub fn write_freeze_info(e: &Env, addr: Address, info: &FreezeInfo) {
+    let key = DataKey::AccountFreezeInfo(addr.clone());
+    e.storage().instance().remove(&key);
+    e.storage().persistent().set(&key, info);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, FREEZE_LIFETIME_THRESHOLD, FREEZE_BUMP_AMOUNT);
+    crate::frozen_index::add_frozen_account(e, addr);
+}
+
+pub fn remove_freeze_info(e: &Env, addr: Address) -> bool {
+    let key = DataKey::AccountFreezeInfo(addr.clone());
+    let existed = e.storage().persistent().has(&key) || e.storage().instance().has(&key);
+    e.storage().persistent().remove(&key);
+    e.storage().instance().remove(&key);
+    crate::frozen_index::remove_frozen_account(e, addr);
+    existed
+}
+
+/// Freezes `addr` on behalf of `operator` and records it in the history.
+pub fn apply_freeze(e: &Env, operator: Address, addr: Address, info: &FreezeInfo) {
+    write_freeze_info(e, addr.clone(), info);
+    crate::history::record_freeze(e, addr, operator, info);
+}
+
+/// Replaces the active freeze on `addr` with `info`, recording the amendment.
+pub fn amend_freeze(e: &Env, operator: Address, addr: Address, info: &FreezeInfo) {
+    write_freeze_info(e, addr.clone(), info);
+    crate::history::record_amendment(e, addr, operator, info);
+}
+
+/// Lifts the freeze on `addr`, if any, and records who lifted it.
+pub fn lift_freeze(e: &Env, operator: Address, addr: Address) -> bool {
+    if remove_freeze_info(e, addr.clone()) {
+        crate::history::record_unfreeze(e, addr, operator);
+        true
+    } else {
+        false
+    }
+}
+
+/// Moves a freeze record written by an older contract version out of
+/// instance storage. A record already present in persistent storage wins.
+pub fn migrate_legacy_freeze_info(e: &Env, addr: Address) -> bool {
+    let key = DataKey::AccountFreezeInfo(addr.clone());
+    if let Some(info) = read_legacy_freeze_info(e, addr.clone()) {
+        if !e.storage().persistent().has(&key) {
+            write_freeze_info(e, addr, &info);
+        } else {
+            e.storage().instance().remove(&key);
+        }
+        true
+    } else {
+        false
+    }
+}
+
+pub fn get_active_freeze_info(e: &Env, account: &Address) -> Option<FreezeInfo> {
+    if let Some(info) = read_freeze_info(e, account.clone()) {
+        if e.ledger().sequence() < info.start_ledger {
+            // Planlanmış, henüz başlamamış dondurma
+            return None;
+        }
+        if info.expires_at != 0 && e.ledger().timestamp() >= info.expires_at {
+            // Zaman damgasına göre süresi dolmuş dondurma
+            return None;
+        }
+        if info.expiration_ledger == 0 {
+            // Süresiz dondurma, her zaman aktif
+            return Some(info);
+        } else {
+            // Süreli dondurma
+            if e.ledger().sequence() < info.expiration_ledger {
+                return Some(info);
+            } else {
+                return None;
+            }
+        }
+    }
+    None
+}
+
+/// Returns the freeze on `account` if it is scheduled but has not started yet.
+pub fn get_pending_freeze_info(e: &Env, account: &Address) -> Option<FreezeInfo> {
+    read_freeze_info(e, account.clone()).filter(|info| e.ledger().sequence() < info.start_ledger)
+}
+
+/// Whether the freeze recorded for `account`, if any, has run its course:
+/// it is neither active nor still waiting to start.
+pub fn is_freeze_lapsed(e: &Env, account: &Address) -> bool {
+    get_active_freeze_info(e, account).is_none() && get_pending_freeze_info(e, account).is_none()
+}
+
+pub fn is_account_effectively_frozen(e: &Env, account: &Address) -> bool {
+    get_active_freeze_info(e, account).is_some()
+}
+
+/// Whether the account is currently blocked from sending, burning or approving.
+pub fn is_outbound_frozen(e: &Env, account: &Address) -> bool {
+    match get_active_freeze_info(e, account) {
+        Some(info) => info.scope != FreezeScope::Inbound,
+        None => false,
+    }
+}
+
+/// Whether the account is currently blocked from receiving tokens.
+pub fn is_inbound_frozen(e: &Env, account: &Address) -> bool {
+    match get_active_freeze_info(e, account) {
+        Some(info) => info.scope != FreezeScope::Outbound,
+        None => false,
+    }
+}
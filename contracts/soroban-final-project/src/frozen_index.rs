@@ -0,0 +1,166 @@
+use crate::storage_types::{
+    ComplianceStatus, DataKey, FrozenAccount, FrozenAccountsPage, FREEZE_BUMP_AMOUNT,
+    FREEZE_LIFETIME_THRESHOLD, MAX_FREEZE_BATCH, MAX_FROZEN_ACCOUNTS_PAGE,
+};
+use soroban_sdk::{Address, Env, Vec};
+
+// Frozen accounts are kept in a dense array of persistent slots
+// (`FrozenAccountAt`), with a reverse lookup (`FrozenAccountSlot`) so removal
+// is a swap with the last slot. Only the slot count lives in instance storage.
+
+fn read_count(e: &Env) -> u32 {
+    e.storage()
+        .instance()
+        .get::<DataKey, u32>(&DataKey::FrozenAccountCount)
+        .unwrap_or(0)
+}
+
+fn write_count(e: &Env, count: u32) {
+    e.storage().instance().set(&DataKey::FrozenAccountCount, &count);
+}
+
+fn read_slot(e: &Env, addr: Address) -> Option<u32> {
+    let key = DataKey::FrozenAccountSlot(addr);
+    let slot = e.storage().persistent().get::<DataKey, u32>(&key)?;
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, FREEZE_LIFETIME_THRESHOLD, FREEZE_BUMP_AMOUNT);
+    Some(slot)
+}
+
+fn read_account_at(e: &Env, slot: u32) -> Option<Address> {
+    let key = DataKey::FrozenAccountAt(slot);
+    let addr = e.storage().persistent().get::<DataKey, Address>(&key)?;
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, FREEZE_LIFETIME_THRESHOLD, FREEZE_BUMP_AMOUNT);
+    Some(addr)
+}
+
+/// Extends the TTL of the index entries of `addr` along with its freeze
+/// record, so a long-lived freeze does not outlive its slot.
+pub fn extend_ttl(e: &Env, addr: Address) {
+    if let Some(slot) = read_slot(e, addr) {
+        read_account_at(e, slot);
+    }
+}
+
+fn write_slot(e: &Env, slot: u32, addr: Address) {
+    let at_key = DataKey::FrozenAccountAt(slot);
+    let slot_key = DataKey::FrozenAccountSlot(addr.clone());
+    e.storage().persistent().set(&at_key, &addr);
+    e.storage().persistent().set(&slot_key, &slot);
+    e.storage()
+        .persistent()
+        .extend_ttl(&at_key, FREEZE_LIFETIME_THRESHOLD, FREEZE_BUMP_AMOUNT);
+    e.storage()
+        .persistent()
+        .extend_ttl(&slot_key, FREEZE_LIFETIME_THRESHOLD, FREEZE_BUMP_AMOUNT);
+}
+
+pub fn add_frozen_account(e: &Env, addr: Address) {
+    if let Some(slot) = read_slot(e, addr.clone()) {
+        write_slot(e, slot, addr);
+        return;
+    }
+    let count = read_count(e);
+    write_slot(e, count, addr);
+    write_count(e, count + 1);
+}
+
+pub fn remove_frozen_account(e: &Env, addr: Address) {
+    let Some(slot) = read_slot(e, addr.clone()) else {
+        return;
+    };
+    let last = read_count(e) - 1;
+    if slot != last {
+        if let Some(moved) = read_account_at(e, last) {
+            write_slot(e, slot, moved);
+        }
+    }
+    e.storage()
+        .persistent()
+        .remove(&DataKey::FrozenAccountAt(last));
+    e.storage()
+        .persistent()
+        .remove(&DataKey::FrozenAccountSlot(addr));
+    write_count(e, last);
+}
+
+/// Scans up to `limit` index slots starting at `cursor` and returns the
+/// accounts among them whose freeze is active (or, with `pending`, scheduled
+/// but not started), optionally restricted to one compliance tag. Timed
+/// freezes that have lapsed stay in the index until `prune_lapsed` removes
+/// them, but are never returned.
+pub fn read_frozen_accounts(
+    e: &Env,
+    cursor: u32,
+    limit: u32,
+    compliance_tag: Option<ComplianceStatus>,
+    pending: bool,
+) -> FrozenAccountsPage {
+    let count = read_count(e);
+    let end = cursor
+        .saturating_add(limit.min(MAX_FROZEN_ACCOUNTS_PAGE))
+        .min(count);
+
+    let mut accounts = Vec::new(e);
+    for slot in cursor..end {
+        let Some(account) = read_account_at(e, slot) else {
+            continue;
+        };
+        let info = if pending {
+            crate::freeze::get_pending_freeze_info(e, &account)
+        } else {
+            crate::freeze::get_active_freeze_info(e, &account)
+        };
+        let Some(info) = info else {
+            continue;
+        };
+        if compliance_tag.is_some_and(|tag| tag != info.compliance_tag) {
+            continue;
+        }
+        accounts.push_back(FrozenAccount { account, info });
+    }
+
+    FrozenAccountsPage {
+        accounts,
+        next_cursor: if end < count { Some(end) } else { None },
+    }
+}
+
+/// Scans up to `limit` index slots starting at `cursor` and deletes the
+/// freeze records that have lapsed, together with their index slots. At most
+/// `MAX_FREEZE_BATCH` records are deleted per call. Returns the cursor to
+/// resume from, or `None` once the end of the index is reached.
+pub fn prune_lapsed(e: &Env, cursor: u32, limit: u32) -> Option<u32> {
+    let mut end = cursor
+        .saturating_add(limit.min(MAX_FROZEN_ACCOUNTS_PAGE))
+        .min(read_count(e));
+    let mut slot = cursor;
+    let mut pruned = 0;
+    while slot < end && pruned < MAX_FREEZE_BATCH {
+        let lapsed = read_account_at(e, slot)
+            .is_some_and(|account| prune_if_lapsed(e, account));
+        if lapsed {
+            // The last slot has been swapped into this one; check it next.
+            pruned += 1;
+            end = end.min(read_count(e));
+        } else {
+            slot += 1;
+        }
+    }
+    if slot < read_count(e) {
+        Some(slot)
+    } else {
+        None
+    }
+}
+
+fn prune_if_lapsed(e: &Env, account: Address) -> bool {
+    if !crate::freeze::is_freeze_lapsed(e, &account) {
+        return false;
+    }
+    crate::freeze::remove_freeze_info(e, account);
+    true
+}
@@ -0,0 +1,137 @@
+use crate::storage_types::{
+    DataKey, FreezeEnd, FreezeHistoryEntry, FreezeInfo, FREEZE_BUMP_AMOUNT,
+    FREEZE_LIFETIME_THRESHOLD, MAX_FREEZE_HISTORY, MAX_FREEZE_HISTORY_PAGE,
+};
+use soroban_sdk::{Address, Env, Vec};
+
+// History is a per-account ring buffer of `MAX_FREEZE_HISTORY` slots. Entry
+// `n` lives in slot `n % MAX_FREEZE_HISTORY`, so only the most recent entries
+// are retained and storage per account stays bounded.
+
+pub fn read_history_len(e: &Env, addr: Address) -> u32 {
+    let key = DataKey::FreezeHistoryLen(addr);
+    if let Some(len) = e.storage().persistent().get::<DataKey, u32>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, FREEZE_LIFETIME_THRESHOLD, FREEZE_BUMP_AMOUNT);
+        len
+    } else {
+        0
+    }
+}
+
+fn write_history_len(e: &Env, addr: Address, len: u32) {
+    let key = DataKey::FreezeHistoryLen(addr);
+    e.storage().persistent().set(&key, &len);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, FREEZE_LIFETIME_THRESHOLD, FREEZE_BUMP_AMOUNT);
+}
+
+fn read_entry(e: &Env, addr: Address, index: u32) -> Option<FreezeHistoryEntry> {
+    let key = DataKey::FreezeHistory(addr, index % MAX_FREEZE_HISTORY);
+    let entry = e.storage().persistent().get::<DataKey, FreezeHistoryEntry>(&key)?;
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, FREEZE_LIFETIME_THRESHOLD, FREEZE_BUMP_AMOUNT);
+    Some(entry)
+}
+
+fn write_entry(e: &Env, addr: Address, index: u32, entry: &FreezeHistoryEntry) {
+    let key = DataKey::FreezeHistory(addr, index % MAX_FREEZE_HISTORY);
+    e.storage().persistent().set(&key, entry);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, FREEZE_LIFETIME_THRESHOLD, FREEZE_BUMP_AMOUNT);
+}
+
+/// An open entry whose timed freeze has already lapsed is reported as expired.
+fn resolve_expiry(e: &Env, mut entry: FreezeHistoryEntry) -> FreezeHistoryEntry {
+    if entry.end != FreezeEnd::Open {
+        return entry;
+    }
+    if entry.expiration_ledger != 0 && entry.expiration_ledger <= e.ledger().sequence() {
+        entry.end = FreezeEnd::Expired(entry.expiration_ledger);
+    } else if entry.expires_at != 0 && entry.expires_at <= e.ledger().timestamp() {
+        entry.end = FreezeEnd::ExpiredAt(entry.expires_at);
+    }
+    entry
+}
+
+/// Closes the latest entry for `addr` if it is still open. Returns whether an
+/// entry was closed.
+fn close_open_entry(e: &Env, addr: &Address, len: u32, end: FreezeEnd) -> bool {
+    if len == 0 {
+        return false;
+    }
+    let Some(entry) = read_entry(e, addr.clone(), len - 1) else {
+        return false;
+    };
+    if entry.end != FreezeEnd::Open {
+        return false;
+    }
+    let mut entry = resolve_expiry(e, entry);
+    if entry.end == FreezeEnd::Open {
+        entry.end = end;
+    }
+    write_entry(e, addr.clone(), len - 1, &entry);
+    true
+}
+
+fn append_entry(e: &Env, addr: Address, frozen_by: Address, info: &FreezeInfo, previous_end: FreezeEnd) {
+    let len = read_history_len(e, addr.clone());
+    close_open_entry(e, &addr, len, previous_end);
+    let entry = FreezeHistoryEntry {
+        frozen_by,
+        frozen_at_ledger: e.ledger().sequence(),
+        start_ledger: info.start_ledger,
+        expiration_ledger: info.expiration_ledger,
+        expires_at: info.expires_at,
+        reason: info.reason.clone(),
+        compliance_tag: info.compliance_tag,
+        scope: info.scope,
+        end: FreezeEnd::Open,
+    };
+    write_entry(e, addr.clone(), len, &entry);
+    write_history_len(e, addr, len + 1);
+}
+
+pub fn record_freeze(e: &Env, addr: Address, frozen_by: Address, info: &FreezeInfo) {
+    let replaced = FreezeEnd::Replaced(frozen_by.clone(), e.ledger().sequence());
+    append_entry(e, addr, frozen_by, info, replaced);
+}
+
+/// Records an amendment as a new entry, closing the one it supersedes.
+pub fn record_amendment(e: &Env, addr: Address, amended_by: Address, info: &FreezeInfo) {
+    let amended = FreezeEnd::Amended(amended_by.clone(), e.ledger().sequence());
+    append_entry(e, addr, amended_by, info, amended);
+}
+
+pub fn record_unfreeze(e: &Env, addr: Address, unfrozen_by: Address) {
+    let len = read_history_len(e, addr.clone());
+    close_open_entry(
+        e,
+        &addr,
+        len,
+        FreezeEnd::Unfrozen(unfrozen_by, e.ledger().sequence()),
+    );
+}
+
+/// Returns up to `limit` entries starting at absolute index `start`, oldest
+/// first. Entries that have been rotated out are skipped.
+pub fn read_history(e: &Env, addr: Address, start: u32, limit: u32) -> Vec<FreezeHistoryEntry> {
+    let len = read_history_len(e, addr.clone());
+    let oldest = len.saturating_sub(MAX_FREEZE_HISTORY);
+    let first = start.max(oldest);
+    let end = first
+        .saturating_add(limit.min(MAX_FREEZE_HISTORY_PAGE))
+        .min(len);
+
+    let mut entries = Vec::new(e);
+    for index in first..end {
+        if let Some(entry) = read_entry(e, addr.clone(), index) {
+            entries.push_back(resolve_expiry(e, entry));
+        }
+    }
+    entries
+}
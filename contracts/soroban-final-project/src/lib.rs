@@ -2,10 +2,27 @@
 
 mod admin;
 mod allowance;
+mod allowlist;
 mod balance;
+mod compliance;
+mod compliance_hook;
 mod contract;
+mod error;
+mod escalation;
+mod freeze;
+mod frozen_index;
+mod history;
+mod lock;
 mod metadata;
+mod minter;
+mod pause;
+mod proposal;
+mod roles;
 mod storage_types;
+mod supply;
 mod test;
+mod upgrade;
+mod velocity;
 
-pub use crate::contract::TokenClient;
\ No newline at end of file
+pub use crate::contract::TokenClient;
+pub use crate::error::{GovernanceError, TokenError};
\ No newline at end of file
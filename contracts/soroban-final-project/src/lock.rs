@@ -0,0 +1,86 @@
+use crate::error::TokenError;
+use crate::storage_types::{
+    AccountLocks, AmountLock, DataKey, FREEZE_BUMP_AMOUNT, FREEZE_LIFETIME_THRESHOLD,
+    MAX_AMOUNT_LOCKS,
+};
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
+
+fn is_lock_active(e: &Env, lock: &AmountLock) -> bool {
+    lock.expiration_ledger == 0 || e.ledger().sequence() < lock.expiration_ledger
+}
+
+fn read_account_locks(e: &Env, addr: Address) -> AccountLocks {
+    let key = DataKey::AmountLocks(addr);
+    if let Some(locks) = e.storage().persistent().get::<DataKey, AccountLocks>(&key) {
+        e.storage()
+            .persistent()
+            .extend_ttl(&key, FREEZE_LIFETIME_THRESHOLD, FREEZE_BUMP_AMOUNT);
+        locks
+    } else {
+        AccountLocks {
+            next_id: 1,
+            locks: Vec::new(e),
+        }
+    }
+}
+
+fn write_account_locks(e: &Env, addr: Address, locks: &AccountLocks) {
+    // The record is kept even when no locks remain so lock ids are never reused.
+    let key = DataKey::AmountLocks(addr);
+    e.storage().persistent().set(&key, locks);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, FREEZE_LIFETIME_THRESHOLD, FREEZE_BUMP_AMOUNT);
+}
+
+/// Returns the locks on `addr` that have not expired yet.
+pub fn read_active_locks(e: &Env, addr: Address) -> Vec<AmountLock> {
+    let mut active = Vec::new(e);
+    for lock in read_account_locks(e, addr).locks.iter() {
+        if is_lock_active(e, &lock) {
+            active.push_back(lock);
+        }
+    }
+    active
+}
+
+pub fn read_locked_amount(e: &Env, addr: Address) -> i128 {
+    read_active_locks(e, addr)
+        .iter()
+        .fold(0i128, |total, lock| total.saturating_add(lock.amount))
+}
+
+/// Adds a lock to `addr`, dropping expired locks first, and returns its id.
+pub fn add_lock(e: &Env, addr: Address, mut lock: AmountLock) -> u32 {
+    let mut account_locks = read_account_locks(e, addr.clone());
+    let mut locks = Vec::new(e);
+    for existing in account_locks.locks.iter() {
+        if is_lock_active(e, &existing) {
+            locks.push_back(existing);
+        }
+    }
+    if locks.len() >= MAX_AMOUNT_LOCKS {
+        panic_with_error!(e, TokenError::TooManyLocks);
+    }
+
+    lock.id = account_locks.next_id;
+    locks.push_back(lock.clone());
+    account_locks.next_id += 1;
+    account_locks.locks = locks;
+    write_account_locks(e, addr, &account_locks);
+    lock.id
+}
+
+/// Removes the lock with `lock_id` from `addr` and returns it.
+pub fn remove_lock(e: &Env, addr: Address, lock_id: u32) -> AmountLock {
+    let mut account_locks = read_account_locks(e, addr.clone());
+    let index = account_locks
+        .locks
+        .iter()
+        .position(|lock| lock.id == lock_id)
+        .unwrap_or_else(|| panic_with_error!(e, TokenError::LockNotFound));
+    let removed = account_locks.locks.get_unchecked(index as u32);
+    account_locks.locks.remove(index as u32);
+    write_account_locks(e, addr, &account_locks);
+    removed
+}
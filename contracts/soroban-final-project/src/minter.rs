@@ -0,0 +1,56 @@
+use crate::error::TokenError;
+use crate::storage_types::{
+    DataKey, MinterAllowance, BALANCE_BUMP_AMOUNT, BALANCE_LIFETIME_THRESHOLD,
+};
+use soroban_sdk::{panic_with_error, Address, Env};
+
+pub fn read_minter(e: &Env, minter: Address) -> Option<MinterAllowance> {
+    let key = DataKey::MinterAllowance(minter);
+    let allowance = e.storage().persistent().get::<DataKey, MinterAllowance>(&key)?;
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    Some(allowance)
+}
+
+pub fn write_minter(e: &Env, minter: Address, allowance: &MinterAllowance) {
+    let key = DataKey::MinterAllowance(minter);
+    e.storage().persistent().set(&key, allowance);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+pub fn remove_minter(e: &Env, minter: Address) -> bool {
+    let key = DataKey::MinterAllowance(minter);
+    let existed = e.storage().persistent().has(&key);
+    e.storage().persistent().remove(&key);
+    existed
+}
+
+fn is_expired(e: &Env, allowance: &MinterAllowance) -> bool {
+    allowance.expiration_ledger != 0 && e.ledger().sequence() >= allowance.expiration_ledger
+}
+
+/// The quota `minter` can still issue; 0 once it has expired.
+pub fn read_quota(e: &Env, minter: Address) -> i128 {
+    match read_minter(e, minter) {
+        Some(allowance) if !is_expired(e, &allowance) => allowance.quota,
+        _ => 0,
+    }
+}
+
+/// Deducts `amount` from the quota of `minter` and returns what is left.
+pub fn spend_quota(e: &Env, minter: Address, amount: i128) -> i128 {
+    let mut allowance = read_minter(e, minter.clone())
+        .unwrap_or_else(|| panic_with_error!(e, TokenError::NotAMinter));
+    if is_expired(e, &allowance) {
+        panic_with_error!(e, TokenError::MinterExpired);
+    }
+    if allowance.quota < amount {
+        panic_with_error!(e, TokenError::MinterQuotaExceeded);
+    }
+    allowance.quota -= amount;
+    write_minter(e, minter, &allowance);
+    allowance.quota
+}
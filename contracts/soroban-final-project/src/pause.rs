@@ -0,0 +1,35 @@
+use crate::error::TokenError;
+use crate::storage_types::{DataKey, PauseInfo};
+use soroban_sdk::{panic_with_error, Env};
+
+pub fn read_pause_info(e: &Env) -> Option<PauseInfo> {
+    let key = DataKey::Pause;
+    e.storage().instance().get(&key)
+}
+
+pub fn write_pause_info(e: &Env, info: &PauseInfo) {
+    let key = DataKey::Pause;
+    e.storage().instance().set(&key, info);
+}
+
+pub fn remove_pause_info(e: &Env) {
+    let key = DataKey::Pause;
+    e.storage().instance().remove(&key);
+}
+
+/// Returns the pause record if the pause is still in effect. Timed pauses
+/// lapse at `expiration_ledger`, like timed freezes.
+pub fn get_active_pause_info(e: &Env) -> Option<PauseInfo> {
+    let info = read_pause_info(e)?;
+    if info.expiration_ledger == 0 || e.ledger().sequence() < info.expiration_ledger {
+        Some(info)
+    } else {
+        None
+    }
+}
+
+pub fn require_not_paused(e: &Env) {
+    if get_active_pause_info(e).is_some() {
+        panic_with_error!(e, TokenError::ContractPaused);
+    }
+}
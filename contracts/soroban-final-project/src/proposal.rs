@@ -0,0 +1,131 @@
+use crate::error::GovernanceError;
+use crate::storage_types::{
+    DataKey, DualControlConfig, Proposal, ProposalStatus, ProposedAction, Role,
+    BALANCE_BUMP_AMOUNT, BALANCE_LIFETIME_THRESHOLD,
+};
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
+
+pub fn read_config(e: &Env) -> Option<DualControlConfig> {
+    e.storage().instance().get(&DataKey::DualControl)
+}
+
+/// Rejects a threshold that the `approvers` Approver role holders could
+/// never meet, which would block every proposal.
+pub fn require_reachable_threshold(e: &Env, approvers: u32) {
+    if read_config(e).is_some_and(|config| config.threshold > approvers) {
+        panic_with_error!(e, GovernanceError::InvalidDualControlConfig);
+    }
+}
+
+pub fn write_config(e: &Env, config: &Option<DualControlConfig>) {
+    match config {
+        Some(config) => {
+            let approvers = crate::roles::read_role_members(e, Role::Approver).len();
+            if config.threshold == 0 || config.threshold > approvers || config.window_ledgers == 0
+            {
+                panic_with_error!(e, GovernanceError::InvalidDualControlConfig);
+            }
+            e.storage().instance().set(&DataKey::DualControl, config);
+        }
+        None => e.storage().instance().remove(&DataKey::DualControl),
+    }
+}
+
+/// Rejects single-key calls to actions that need approval while dual
+/// control is enabled.
+pub fn require_single_key_allowed(e: &Env) {
+    if read_config(e).is_some() {
+        panic_with_error!(e, GovernanceError::DualControlRequired);
+    }
+}
+
+pub fn read_proposal(e: &Env, id: u32) -> Proposal {
+    let key = DataKey::Proposal(id);
+    let proposal = e
+        .storage()
+        .persistent()
+        .get::<DataKey, Proposal>(&key)
+        .unwrap_or_else(|| panic_with_error!(e, GovernanceError::ProposalNotFound));
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    proposal
+}
+
+fn write_proposal(e: &Env, proposal: &Proposal) {
+    let key = DataKey::Proposal(proposal.id);
+    e.storage().persistent().set(&key, proposal);
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
+
+/// Stores a new pending proposal under a fresh id, using the current
+/// threshold and approval window. Ids are never reused.
+pub fn create_proposal(e: &Env, proposer: Address, action: ProposedAction) -> Proposal {
+    let config =
+        read_config(e).unwrap_or_else(|| panic_with_error!(e, GovernanceError::DualControlDisabled));
+    let id = e
+        .storage()
+        .instance()
+        .get::<DataKey, u32>(&DataKey::NextProposalId)
+        .unwrap_or(1);
+    let expiration_ledger = e
+        .ledger()
+        .sequence()
+        .checked_add(config.window_ledgers)
+        .unwrap_or_else(|| panic_with_error!(e, GovernanceError::InvalidDualControlConfig));
+    let proposal = Proposal {
+        id,
+        proposer,
+        action,
+        approvals: Vec::new(e),
+        threshold: config.threshold,
+        expiration_ledger,
+        status: ProposalStatus::Pending,
+    };
+    write_proposal(e, &proposal);
+    e.storage()
+        .instance()
+        .set(&DataKey::NextProposalId, &(id + 1));
+    proposal
+}
+
+fn require_pending(e: &Env, proposal: &Proposal) {
+    if proposal.status != ProposalStatus::Pending {
+        panic_with_error!(e, GovernanceError::ProposalNotPending);
+    }
+}
+
+/// Records the approval of `approver` and returns the updated proposal. The
+/// proposal is marked executed once it reaches its threshold; the caller
+/// carries out the action.
+pub fn approve_proposal(e: &Env, id: u32, approver: Address) -> Proposal {
+    let mut proposal = read_proposal(e, id);
+    require_pending(e, &proposal);
+    if e.ledger().sequence() >= proposal.expiration_ledger {
+        panic_with_error!(e, GovernanceError::ProposalExpired);
+    }
+    if approver == proposal.proposer {
+        panic_with_error!(e, GovernanceError::ProposerCannotApprove);
+    }
+    if proposal.approvals.contains(&approver) {
+        panic_with_error!(e, GovernanceError::AlreadyApproved);
+    }
+    proposal.approvals.push_back(approver);
+    if proposal.approvals.len() >= proposal.threshold {
+        proposal.status = ProposalStatus::Executed;
+    }
+    write_proposal(e, &proposal);
+    proposal
+}
+
+/// Closes a pending proposal as rejected or cancelled. Expired proposals can
+/// still be closed.
+pub fn close_proposal(e: &Env, id: u32, status: ProposalStatus) -> Proposal {
+    let mut proposal = read_proposal(e, id);
+    require_pending(e, &proposal);
+    proposal.status = status;
+    write_proposal(e, &proposal);
+    proposal
+}
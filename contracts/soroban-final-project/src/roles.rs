@@ -0,0 +1,74 @@
+use crate::error::TokenError;
+use crate::storage_types::{DataKey, Role, MAX_ROLE_MEMBERS};
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
+
+pub fn read_role_members(e: &Env, role: Role) -> Vec<Address> {
+    let key = DataKey::RoleMembers(role);
+    e.storage()
+        .instance()
+        .get::<DataKey, Vec<Address>>(&key)
+        .unwrap_or_else(|| Vec::new(e))
+}
+
+fn write_role_members(e: &Env, role: Role, members: &Vec<Address>) {
+    let key = DataKey::RoleMembers(role);
+    if members.is_empty() {
+        e.storage().instance().remove(&key);
+    } else {
+        e.storage().instance().set(&key, members);
+    }
+}
+
+pub fn has_role(e: &Env, role: Role, addr: &Address) -> bool {
+    read_role_members(e, role).contains(addr)
+}
+
+pub fn grant_role(e: &Env, role: Role, addr: Address) {
+    let mut members = read_role_members(e, role);
+    if members.contains(&addr) {
+        panic_with_error!(e, TokenError::RoleAlreadyGranted);
+    }
+    if members.len() >= MAX_ROLE_MEMBERS {
+        panic_with_error!(e, TokenError::TooManyRoleMembers);
+    }
+    members.push_back(addr);
+    write_role_members(e, role, &members);
+}
+
+pub fn revoke_role(e: &Env, role: Role, addr: Address) {
+    let mut members = read_role_members(e, role);
+    let index = members
+        .first_index_of(&addr)
+        .unwrap_or_else(|| panic_with_error!(e, TokenError::RoleNotGranted));
+    members.remove(index);
+    if role == Role::Approver {
+        crate::proposal::require_reachable_threshold(e, members.len());
+    }
+    write_role_members(e, role, &members);
+}
+
+/// Requires `caller` to have authorized the call and to hold `role`.
+/// The administrator implicitly holds every role, as long as a single
+/// admin signer is enough to act for it.
+pub fn require_role(e: &Env, caller: &Address, role: Role) {
+    caller.require_auth();
+    if crate::admin::is_sole_admin(e, caller) {
+        return;
+    }
+    if !has_role(e, role, caller) {
+        panic_with_error!(e, TokenError::MissingRole);
+    }
+}
+
+/// Requires `signers` to be either a single holder of `role`, which is only
+/// enough while the admin set has threshold 1, or an admin quorum as checked
+/// by `admin::require_admin`. Returns the first signer, recorded as the actor.
+pub fn require_role_or_admin(e: &Env, signers: &Vec<Address>, role: Role) -> Address {
+    if signers.len() == 1 && crate::admin::read_admin_set(e).threshold == 1 {
+        let caller = signers.get_unchecked(0);
+        require_role(e, &caller, role);
+        return caller;
+    }
+    crate::admin::require_admin(e, signers);
+    signers.get_unchecked(0)
+}
@@ -1,10 +1,37 @@
-use soroban_sdk::{contracttype, Address, String};
+use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
 
 pub(crate) const DAY_IN_LEDGERS: u32 = 17280;
+pub(crate) const CURRENT_SCHEMA_VERSION: u32 = 2;
 pub(crate) const INSTANCE_BUMP_AMOUNT: u32 = 7 * DAY_IN_LEDGERS;
 pub(crate) const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
 pub(crate) const BALANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
 pub(crate) const BALANCE_LIFETIME_THRESHOLD: u32 = BALANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+pub(crate) const FREEZE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+pub(crate) const FREEZE_LIFETIME_THRESHOLD: u32 = FREEZE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+pub(crate) const MAX_AMOUNT_LOCKS: u32 = 20;
+pub(crate) const MAX_ROLE_MEMBERS: u32 = 20;
+pub(crate) const MAX_FREEZE_HISTORY: u32 = 50;
+pub(crate) const MAX_FREEZE_HISTORY_PAGE: u32 = 50;
+pub(crate) const MAX_FROZEN_ACCOUNTS_PAGE: u32 = 50;
+/// Ledger entries a single transaction may write on the network.
+pub(crate) const TX_MAX_WRITE_ENTRIES: u32 = 25;
+/// Entries written per account by a batch freeze or unfreeze, in the worst
+/// case. Re-freezing writes the freeze record, both index slots, the new
+/// history entry, the history length and the entry it closes; unfreezing
+/// writes the record, the closed history entry and four index slots when
+/// another account is swapped into the freed slot.
+pub(crate) const FREEZE_WRITE_ENTRIES: u32 = 6;
+/// Upper bound on `freeze_accounts` / `unfreeze_accounts` entries per call,
+/// so that a full batch stays within `TX_MAX_WRITE_ENTRIES` alongside the
+/// contract instance and the authorization nonce.
+pub(crate) const MAX_FREEZE_BATCH: u32 = (TX_MAX_WRITE_ENTRIES - 2) / FREEZE_WRITE_ENTRIES;
+pub(crate) const MAX_COMPLIANCE_HISTORY: u32 = 20;
+pub(crate) const MAX_ALLOWLIST_BATCH: u32 = 50;
+pub(crate) const VELOCITY_BUCKETS: u32 = 24;
+pub(crate) const MAX_ESCALATION_RULES: u32 = 10;
+/// Longest escalation rule reason, in bytes.
+pub(crate) const MAX_RULE_REASON_LEN: u32 = 200;
+pub(crate) const MAX_ADMIN_SIGNERS: u32 = 10;
 
 
 #[derive(Clone, Debug, PartialEq, Eq, Copy)]
@@ -17,14 +44,299 @@ pub enum ComplianceStatus {
     Other,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Copy)]
+#[contracttype]
+pub enum Role {
+    /// May mint new tokens.
+    Minter,
+    /// May freeze and unfreeze accounts and amounts.
+    Freezer,
+    /// May change the compliance tag of frozen accounts.
+    ComplianceOfficer,
+    /// May grant and revoke roles.
+    AccessManager,
+    /// May pause and unpause all token movement.
+    Guardian,
+    /// May propose mints, unfreezes and clawbacks under dual control.
+    Proposer,
+    /// May approve or reject proposals under dual control.
+    Approver,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Copy)]
+#[contracttype]
+pub enum FreezeScope {
+    /// The account cannot send, burn or approve, but can still receive.
+    Outbound,
+    /// The account cannot receive tokens, but can still send them.
+    Inbound,
+    /// The account can neither send nor receive.
+    Full,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[contracttype]
 pub struct FreezeInfo {
+    /// First ledger at which the freeze applies; later than the current
+    /// ledger for a scheduled freeze.
+    pub start_ledger: u32,
+    pub expiration_ledger: u32,
+    /// Unix time (seconds) at which the freeze lapses, or 0 for none. Applies
+    /// in addition to `expiration_ledger`.
+    pub expires_at: u64,
+    pub reason: String,
+    pub compliance_tag: ComplianceStatus,
+    pub scope: FreezeScope,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum FreezeEnd {
+    /// The freeze is still in force.
+    Open,
+    /// The timed freeze lapsed at the given ledger.
+    Expired(u32),
+    /// The timed freeze lapsed at the given unix time.
+    ExpiredAt(u64),
+    /// Lifted early by the given address at the given ledger.
+    Unfrozen(Address, u32),
+    /// Overwritten by a new freeze from the given address at the given ledger.
+    Replaced(Address, u32),
+    /// Superseded by an amendment from the given address at the given ledger.
+    Amended(Address, u32),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct FreezeHistoryEntry {
+    pub frozen_by: Address,
+    pub frozen_at_ledger: u32,
+    pub start_ledger: u32,
+    pub expiration_ledger: u32,
+    pub expires_at: u64,
+    pub reason: String,
+    pub compliance_tag: ComplianceStatus,
+    pub scope: FreezeScope,
+    pub end: FreezeEnd,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct FrozenAccount {
+    pub account: Address,
+    pub info: FreezeInfo,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct FrozenAccountsPage {
+    pub accounts: Vec<FrozenAccount>,
+    /// Cursor for the next call, or `None` once the index is exhausted.
+    pub next_cursor: Option<u32>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct ComplianceRecord {
+    pub status: ComplianceStatus,
+    /// Hash of the off-chain evidence supporting the status.
+    pub evidence_hash: BytesN<32>,
+    pub set_by: Address,
+    pub set_at_ledger: u32,
+}
+
+/// Operations allowed for accounts holding a given compliance status.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct CompliancePolicy {
+    /// Whether the account may transfer or burn its tokens.
+    pub can_send: bool,
+    /// Whether the account may receive transfers and mints.
+    pub can_receive: bool,
+    /// Largest amount the account may move in a single operation.
+    pub send_limit: Option<i128>,
+}
+
+/// Caps the outflow of an account to `amount` per rolling window of
+/// `window_ledgers` ledgers (e.g. `DAY_IN_LEDGERS`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct VelocityLimit {
+    pub amount: i128,
+    pub window_ledgers: u32,
+}
+
+/// How much of its velocity limit an account has used in the current window.
+/// `next_release_ledger` is the ledger at which the oldest outflow still
+/// counted leaves the window, or 0 if nothing is counted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct VelocityUsage {
+    pub limit: VelocityLimit,
+    pub spent: i128,
+    pub remaining: i128,
+    pub next_release_ledger: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct OutflowBucket {
+    pub index: u32,
+    pub amount: i128,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct OutflowCounter {
+    pub bucket_ledgers: u32,
+    pub buckets: Vec<OutflowBucket>,
+}
+
+/// Condition under which an escalation rule freezes an account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum EscalationTrigger {
+    /// A sender tagged with the status moves more than the amount in a single
+    /// transfer; the sender is frozen.
+    SendAbove(ComplianceStatus, i128),
+    /// An account receives a transfer from a sender tagged with the status;
+    /// the recipient is frozen.
+    ReceiveFrom(ComplianceStatus),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct EscalationRule {
+    pub id: u32,
+    pub trigger: EscalationTrigger,
+    pub compliance_tag: ComplianceStatus,
+    pub scope: FreezeScope,
+    /// 0 freezes indefinitely.
+    pub duration_ledgers: u32,
+    pub reason: String,
+}
+
+/// Issuance delegated to a minter through `set_minter`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct MinterAllowance {
+    /// Amount the minter can still issue.
+    pub quota: i128,
+    /// Ledger at which the delegation lapses, or 0 for none.
+    pub expiration_ledger: u32,
+}
+
+/// Dual control settings. While set, `mint`, `mint_as`, `unfreeze_account`,
+/// `unfreeze_accounts`, `release_amount`, `clawback` and `forced_transfer`
+/// are refused, as is replacing an active freeze, and tokens are only
+/// minted, unfrozen, released or clawed back through proposals.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct DualControlConfig {
+    /// Distinct approvals, besides the proposer, needed to execute.
+    pub threshold: u32,
+    /// Ledgers a proposal stays open for approval.
+    pub window_ledgers: u32,
+}
+
+/// An action that needs approval while dual control is enabled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum ProposedAction {
+    /// Mint `amount` to the address.
+    Mint(Address, i128),
+    /// Lift the freeze on the address.
+    Unfreeze(Address),
+    /// Claw back `amount` from the address, with a reason.
+    Clawback(Address, i128, String),
+    /// Release the amount lock with the given id on the address.
+    ReleaseAmount(Address, u32),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Copy)]
+#[contracttype]
+pub enum ProposalStatus {
+    Pending,
+    Executed,
+    Rejected,
+    Cancelled,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct Proposal {
+    pub id: u32,
+    pub proposer: Address,
+    pub action: ProposedAction,
+    pub approvals: Vec<Address>,
+    /// Approvals required, fixed when the proposal is created.
+    pub threshold: u32,
+    /// First ledger at which the proposal can no longer be approved.
+    pub expiration_ledger: u32,
+    pub status: ProposalStatus,
+}
+
+/// Freeze record layout written by contract versions that predate
+/// `FreezeScope`. Those freezes only ever blocked the sending side.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct LegacyFreezeInfo {
     pub expiration_ledger: u32,
     pub reason: String,
     pub compliance_tag: ComplianceStatus,
 }
 
+/// The administrator: `threshold` of `signers` must authorize every
+/// admin-only call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct AdminSet {
+    pub signers: Vec<Address>,
+    pub threshold: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct PendingAdmin {
+    pub new_admin: Address,
+    /// First ledger at which `new_admin` may accept.
+    pub accept_after_ledger: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct PauseInfo {
+    pub paused_by: Address,
+    pub reason: String,
+    /// 0 means the pause lasts until `unpause` is called.
+    pub expiration_ledger: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct SupplyCap {
+    pub max_supply: i128,
+    /// Whether `update_max_supply` may raise the cap. Lowering is always allowed.
+    pub raisable: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct AmountLock {
+    pub id: u32,
+    pub amount: i128,
+    pub expiration_ledger: u32,
+    pub reason: String,
+    pub compliance_tag: ComplianceStatus,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct AccountLocks {
+    pub next_id: u32,
+    pub locks: Vec<AmountLock>,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub struct AllowanceDataKey {
@@ -43,6 +355,34 @@ pub struct AllowanceValue {
 pub enum DataKey {
     Allowance(AllowanceDataKey),
     Balance(Address),
+    /// Single admin address written by versions before `AdminSet`.
     Admin,
+    AdminSet,
+    PendingAdmin,
+    TotalSupply,
+    SupplyCap,
+    Pause,
+    SchemaVersion,
     AccountFreezeInfo(Address),
+    FreezeHistory(Address, u32),
+    FreezeHistoryLen(Address),
+    FrozenAccountCount,
+    FrozenAccountAt(u32),
+    FrozenAccountSlot(Address),
+    ComplianceHistory(Address),
+    CompliancePolicy(ComplianceStatus),
+    AllowlistMode,
+    Allowlisted(Address),
+    ComplianceContract,
+    DefaultVelocityLimit,
+    VelocityLimit(Address),
+    Outflows(Address),
+    EscalationRules,
+    NextEscalationRuleId,
+    MinterAllowance(Address),
+    DualControl,
+    NextProposalId,
+    Proposal(u32),
+    AmountLocks(Address),
+    RoleMembers(Role),
 }
\ No newline at end of file
@@ -0,0 +1,43 @@
+use crate::error::TokenError;
+use crate::storage_types::{DataKey, SupplyCap};
+use soroban_sdk::{panic_with_error, Env};
+
+pub fn read_total_supply(e: &Env) -> i128 {
+    let key = DataKey::TotalSupply;
+    e.storage().instance().get(&key).unwrap_or(0)
+}
+
+fn write_total_supply(e: &Env, amount: i128) {
+    let key = DataKey::TotalSupply;
+    e.storage().instance().set(&key, &amount);
+}
+
+pub fn read_supply_cap(e: &Env) -> Option<SupplyCap> {
+    let key = DataKey::SupplyCap;
+    e.storage().instance().get(&key)
+}
+
+pub fn write_supply_cap(e: &Env, cap: &SupplyCap) {
+    let key = DataKey::SupplyCap;
+    e.storage().instance().set(&key, cap);
+}
+
+pub fn increase_supply(e: &Env, amount: i128) {
+    let supply = read_total_supply(e)
+        .checked_add(amount)
+        .unwrap_or_else(|| panic_with_error!(e, TokenError::SupplyOverflow));
+    if let Some(cap) = read_supply_cap(e) {
+        if supply > cap.max_supply {
+            panic_with_error!(e, TokenError::MaxSupplyExceeded);
+        }
+    }
+    write_total_supply(e, supply);
+}
+
+pub fn decrease_supply(e: &Env, amount: i128) {
+    let supply = read_total_supply(e);
+    if supply < amount {
+        panic_with_error!(e, TokenError::InsufficientBalance);
+    }
+    write_total_supply(e, supply - amount);
+}
@@ -2,21 +2,90 @@
 extern crate std;
 
 use crate::{
-    storage_types::ComplianceStatus,
-    TokenClient,
+    storage_types::{
+        AdminSet, CompliancePolicy, ComplianceStatus, DataKey, DualControlConfig, EscalationTrigger,
+        ProposalStatus, ProposedAction, VelocityLimit, VelocityUsage, FreezeEnd, FreezeInfo, FreezeScope, LegacyFreezeInfo, Role,
+        CURRENT_SCHEMA_VERSION, DAY_IN_LEDGERS, MAX_ALLOWLIST_BATCH, MAX_FREEZE_BATCH, MAX_FREEZE_HISTORY, TX_MAX_WRITE_ENTRIES,
+        FREEZE_WRITE_ENTRIES,
+    },
+    GovernanceError, TokenClient, TokenError,
 };
 use soroban_sdk::{
     testutils::{
+        storage::{Instance as _, Persistent as _},
+        AuthorizedFunction, AuthorizedInvocation, EnvTestConfig, Events,
         Address as _, Ledger, LedgerInfo,
         MockAuth, MockAuthInvoke,
     },
-    Address, Env, IntoVal, String, Symbol, Val, BytesN,
+    vec, Address, Env, Error, InvokeError, IntoVal, TryFromVal, String, Symbol, Val, Vec, BytesN,
 };
 
+// The contracts under test are loaded from their release wasm, which is not
+// rebuilt by `cargo test`; run `make test` or build the wasm target first.
 static WASM_BYTES: &[u8] = include_bytes!(
     "../../../target/wasm32v1-none/release/soroban_final_project.wasm"
 );
 
+static UPGRADE_FIXTURE_WASM_BYTES: &[u8] = include_bytes!(
+    "../../../target/wasm32v1-none/release/upgrade_fixture.wasm"
+);
+
+
+mod mock_compliance {
+    use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Vec};
+
+    #[contracttype]
+    enum MockKey {
+        Denied(Address),
+        Notifications,
+    }
+
+    /// Stand-in for a shared compliance contract: denies listed accounts and
+    /// records every `transferred` notification.
+    #[contract]
+    pub struct MockCompliance;
+
+    #[contractimpl]
+    impl MockCompliance {
+        pub fn deny(env: Env, account: Address) {
+            env.storage().instance().set(&MockKey::Denied(account), &true);
+        }
+
+        pub fn can_transfer(
+            env: Env,
+            _token: Address,
+            from: Option<Address>,
+            to: Option<Address>,
+            _amount: i128,
+        ) -> bool {
+            let denied = |account: Option<Address>| {
+                account.is_some_and(|account| env.storage().instance().has(&MockKey::Denied(account)))
+            };
+            !denied(from) && !denied(to)
+        }
+
+        pub fn transferred(
+            env: Env,
+            token: Address,
+            from: Option<Address>,
+            to: Option<Address>,
+            amount: i128,
+        ) {
+            let mut notifications = Self::notifications(env.clone());
+            notifications.push_back((token, from, to, amount));
+            env.storage().instance().set(&MockKey::Notifications, &notifications);
+        }
+
+        pub fn notifications(
+            env: Env,
+        ) -> Vec<(Address, Option<Address>, Option<Address>, i128)> {
+            env.storage()
+                .instance()
+                .get(&MockKey::Notifications)
+                .unwrap_or_else(|| Vec::new(&env))
+        }
+    }
+}
 
 fn create_token_and_init<'a>(e: &Env, admin: &Address) -> TokenClient<'a> {
     let deployer_address = Address::generate(e);
@@ -36,6 +105,40 @@ fn create_token_and_init<'a>(e: &Env, admin: &Address) -> TokenClient<'a> {
     token
 }
 
+fn assert_token_error<T: core::fmt::Debug, C: core::fmt::Debug>(
+    result: Result<Result<T, C>, Result<Error, InvokeError>>,
+    expected: impl Into<Error>,
+) {
+    assert_eq!(result.err(), Some(Ok(expected.into())));
+}
+
+fn assert_last_event(e: &Env, contract: &Address, topics: Vec<Val>, data: Val) {
+    let last = e.events().all().last().expect("an event should have been published");
+    assert_eq!(vec![e, last], vec![e, (contract.clone(), topics, data)]);
+}
+
+fn advance_time(e: &Env, seconds: u64) {
+    e.ledger().with_mut(|ledger| ledger.timestamp += seconds);
+}
+
+fn advance_sequence(e: &Env, ledgers: u32) {
+    e.ledger().with_mut(|ledger| ledger.sequence_number += ledgers);
+}
+
+fn count_events(e: &Env, name: &str) -> usize {
+    let name = Symbol::new(e, name);
+    e.events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            topics
+                .get(0)
+                .and_then(|topic| Symbol::try_from_val(e, &topic).ok())
+                == Some(name.clone())
+        })
+        .count()
+}
+
 fn jump_ledgers(e: &Env, ledgers_to_jump: u32) {
     let mut current_ledger_info = e.ledger().get();
     let _original_sequence = current_ledger_info.sequence_number;
@@ -87,7 +190,7 @@ fn test_mint_and_balance_check() {
     let token = create_token_and_init(&e, &admin);
 
     assert_eq!(token.balance(&user1), 0);
-    token.mint(&user1, &1000);
+    token.mint(&vec![&e, admin.clone()], &user1, &1000);
     assert_eq!(token.balance(&user1), 1000);
 }
 
@@ -101,7 +204,7 @@ fn test_approve_allowance_transfer_from() {
     let recipient = Address::generate(&e);
     let token = create_token_and_init(&e, &admin);
 
-    token.mint(&owner, &1000);
+    token.mint(&vec![&e, admin.clone()], &owner, &1000);
     let current_ledger = e.ledger().sequence();
     let expiration_ledger = current_ledger + 100;
 
@@ -124,7 +227,7 @@ fn test_timed_freeze_with_reason_and_compliance() {
     let user_b = Address::generate(&e);
     let token = create_token_and_init(&e, &admin);
 
-    token.mint(&user_a, &5000);
+    token.mint(&vec![&e, admin.clone()], &user_a, &5000);
 
     let freeze_duration: u32 = 10;
     let freeze_reason = String::from_str(&e, "Şüpheli Aktivite İncelemesi");
@@ -133,10 +236,12 @@ fn test_timed_freeze_with_reason_and_compliance() {
     let ledger_before_freeze = e.ledger().sequence();
 
     token.freeze_account(
+        &vec![&e, admin.clone()],
         &user_a,
         &freeze_duration,
         &freeze_reason,
         &compliance_details,
+        &FreezeScope::Outbound,
     );
 
     assert!(token.is_frozen(&user_a));
@@ -178,10 +283,10 @@ fn test_indefinite_freeze_and_manual_unfreeze() {
     let admin = Address::generate(&e);
     let user_c = Address::generate(&e);
     let token = create_token_and_init(&e, &admin);
-    token.mint(&user_c, &500);
+    token.mint(&vec![&e, admin.clone()], &user_c, &500);
 
     let reason = String::from_str(&e, "Kalıcı Uyumluluk İhlali");
-    token.freeze_account(&user_c, &0, &reason.clone(), &ComplianceStatus::Sanctioned);
+    token.freeze_account(&vec![&e, admin.clone()], &user_c, &0, &reason.clone(), &ComplianceStatus::Sanctioned, &FreezeScope::Outbound);
 
     assert!(token.is_frozen(&user_c));
     let info = token.get_account_freeze_details(&user_c).unwrap();
@@ -192,15 +297,14 @@ fn test_indefinite_freeze_and_manual_unfreeze() {
     jump_ledgers(&e, 10000);
     assert!(token.is_frozen(&user_c));
 
-    token.unfreeze_account(&user_c);
+    token.unfreeze_account(&vec![&e, admin.clone()], &user_c);
     assert!(!token.is_frozen(&user_c));
     assert!(token.get_account_freeze_details(&user_c).is_none());
     assert_eq!(token.get_compliance_tag_for_account(&user_c), ComplianceStatus::None);
 }
 
 #[test]
-#[should_panic]
-fn test_approve_on_frozen_account_should_panic() {
+fn test_approve_on_frozen_account_fails() {
     let e = Env::default();
     e.mock_all_auths();
     let admin = Address::generate(&e);
@@ -208,21 +312,25 @@ fn test_approve_on_frozen_account_should_panic() {
     let spender = Address::generate(&e);
     let token = create_token_and_init(&e, &admin);
 
-    token.mint(&user_frozen, &1000);
+    token.mint(&vec![&e, admin.clone()], &user_frozen, &1000);
     token.freeze_account(
+        &vec![&e, admin.clone()],
         &user_frozen,
         &0,
         &String::from_str(&e, "approve_test_freeze"),
         &ComplianceStatus::KycPending,
+        &FreezeScope::Outbound,
     );
 
     let current_ledger = e.ledger().sequence();
-    token.approve(&user_frozen, &spender, &100, &(current_ledger + 100));
+    assert_token_error(
+        token.try_approve(&user_frozen, &spender, &100, &(current_ledger + 100)),
+        TokenError::AccountFrozen,
+    );
 }
 
 #[test]
-#[should_panic]
-fn test_transfer_on_frozen_account_should_panic() {
+fn test_transfer_on_frozen_account_fails() {
     let e = Env::default();
     e.mock_all_auths();
     let admin = Address::generate(&e);
@@ -230,19 +338,23 @@ fn test_transfer_on_frozen_account_should_panic() {
     let recipient = Address::generate(&e);
     let token = create_token_and_init(&e, &admin);
 
-    token.mint(&user_frozen, &1000);
+    token.mint(&vec![&e, admin.clone()], &user_frozen, &1000);
     token.freeze_account(
+        &vec![&e, admin.clone()],
         &user_frozen,
         &10,
         &String::from_str(&e, "transfer_test_freeze"),
         &ComplianceStatus::None,
+        &FreezeScope::Outbound,
+    );
+    assert_token_error(
+        token.try_transfer(&user_frozen, &recipient, &100),
+        TokenError::AccountFrozen,
     );
-    token.transfer(&user_frozen, &recipient, &100);
 }
 
 #[test]
-#[should_panic]
-fn test_transfer_from_on_frozen_account_panics() {
+fn test_transfer_from_on_frozen_account_fails() {
     let e = Env::default();
     e.mock_all_auths();
     let admin = Address::generate(&e);
@@ -251,31 +363,32 @@ fn test_transfer_from_on_frozen_account_panics() {
     let recipient = Address::generate(&e);
     let token = create_token_and_init(&e, &admin);
 
-    token.mint(&owner_frozen, &1000);
+    token.mint(&vec![&e, admin.clone()], &owner_frozen, &1000);
     let current_ledger = e.ledger().sequence();
     token.approve(&owner_frozen, &spender, &500, &(current_ledger + 100));
-    token.freeze_account(&owner_frozen, &0, &String::from_str(&e, "donduruldu"), &ComplianceStatus::Other);
+    token.freeze_account(&vec![&e, admin.clone()], &owner_frozen, &0, &String::from_str(&e, "donduruldu"), &ComplianceStatus::Other, &FreezeScope::Outbound);
 
-    token.transfer_from(&spender, &owner_frozen, &recipient, &100);
+    assert_token_error(
+        token.try_transfer_from(&spender, &owner_frozen, &recipient, &100),
+        TokenError::AccountFrozen,
+    );
 }
 
 #[test]
-#[should_panic]
-fn test_burn_on_frozen_account_panics() {
+fn test_burn_on_frozen_account_fails() {
     let e = Env::default();
     e.mock_all_auths();
     let admin = Address::generate(&e);
     let user1 = Address::generate(&e);
     let token = create_token_and_init(&e, &admin);
 
-    token.mint(&user1, &1000);
-    token.freeze_account(&user1, &0, &String::from_str(&e, "donduruldu"), &ComplianceStatus::None);
-    token.burn(&user1, &100);
+    token.mint(&vec![&e, admin.clone()], &user1, &1000);
+    token.freeze_account(&vec![&e, admin.clone()], &user1, &0, &String::from_str(&e, "donduruldu"), &ComplianceStatus::None, &FreezeScope::Outbound);
+    assert_token_error(token.try_burn(&user1, &100), TokenError::AccountFrozen);
 }
 
 #[test]
-#[should_panic]
-fn test_burn_from_on_frozen_account_panics() {
+fn test_burn_from_on_frozen_account_fails() {
     let e = Env::default();
     e.mock_all_auths();
     let admin = Address::generate(&e);
@@ -283,12 +396,15 @@ fn test_burn_from_on_frozen_account_panics() {
     let spender = Address::generate(&e);
     let token = create_token_and_init(&e, &admin);
 
-    token.mint(&owner_frozen, &1000);
+    token.mint(&vec![&e, admin.clone()], &owner_frozen, &1000);
     let current_ledger = e.ledger().sequence();
     token.approve(&owner_frozen, &spender, &500, &(current_ledger + 100));
-    token.freeze_account(&owner_frozen, &0, &String::from_str(&e, "donduruldu"), &ComplianceStatus::Other);
+    token.freeze_account(&vec![&e, admin.clone()], &owner_frozen, &0, &String::from_str(&e, "donduruldu"), &ComplianceStatus::Other, &FreezeScope::Outbound);
 
-    token.burn_from(&spender, &owner_frozen, &100);
+    assert_token_error(
+        token.try_burn_from(&spender, &owner_frozen, &100),
+        TokenError::AccountFrozen,
+    );
 }
 
 
@@ -298,7 +414,7 @@ fn test_non_admin_cannot_freeze_account() {
     let e = Env::default();
 
     let real_admin = Address::generate(&e);
-    let _non_admin_user = Address::generate(&e);
+    let non_admin_user = Address::generate(&e);
     let account_to_be_frozen = Address::generate(&e);
 
     let deployer_address = Address::generate(&e);
@@ -337,32 +453,35 @@ fn test_non_admin_cannot_freeze_account() {
     let client_as_non_admin = TokenClient::new(&e, &token_id);
 
     client_as_non_admin.freeze_account(
+        &vec![&e, non_admin_user.clone()],
         &account_to_be_frozen,
         &0,
         &String::from_str(&e, "Yetkisiz Deneme"),
         &ComplianceStatus::Other,
+        &FreezeScope::Outbound,
     );
 }
 
 #[test]
-#[should_panic]
-fn test_initialize_already_initialized_panics() {
+fn test_initialize_already_initialized_fails() {
     let e = Env::default();
     e.mock_all_auths();
     let admin = Address::generate(&e);
     let token = create_token_and_init(&e, &admin);
 
-    token.initialize(
-        &admin,
-        &10,
-        &String::from_str(&e, "Ikinci Token"),
-        &String::from_str(&e, "IKT"),
+    assert_token_error(
+        token.try_initialize(
+            &admin,
+            &10,
+            &String::from_str(&e, "Ikinci Token"),
+            &String::from_str(&e, "IKT"),
+        ),
+        TokenError::AlreadyInitialized,
     );
 }
 
 #[test]
-#[should_panic]
-fn test_decimal_over_max_panics() {
+fn test_decimal_over_max_fails() {
     let e = Env::default();
     e.mock_all_auths();
     let admin = Address::generate(&e);
@@ -376,18 +495,20 @@ fn test_decimal_over_max_panics() {
 
     let token = TokenClient::new(&e, &token_id);
 
-    token.initialize(
-        &admin,
-        &(u32::from(u8::MAX) + 1),
-        &String::from_str(&e, "Decimal Test"),
-        &String::from_str(&e, "DCT"),
+    assert_token_error(
+        token.try_initialize(
+            &admin,
+            &(u32::from(u8::MAX) + 1),
+            &String::from_str(&e, "Decimal Test"),
+            &String::from_str(&e, "DCT"),
+        ),
+        TokenError::DecimalTooLarge,
     );
 }
 
 
 #[test]
-#[should_panic(expected = "InvalidAction")]
-fn test_freeze_duration_overflow_panics() {
+fn test_freeze_duration_overflow_fails() {
     let e = Env::default();
     e.mock_all_auths();
     let admin = Address::generate(&e);
@@ -397,10 +518,2492 @@ fn test_freeze_duration_overflow_panics() {
     jump_ledgers(&e, 1);
     assert_eq!(e.ledger().get().sequence_number, 1, "Ledger should be 1 before overflow test");
 
+    assert_token_error(
+        token.try_freeze_account(
+            &vec![&e, admin.clone()],
+            &user_a,
+            &u32::MAX,
+            &String::from_str(&e, "Taşma Testi"),
+            &ComplianceStatus::None,
+            &FreezeScope::Outbound,
+        ),
+        TokenError::DurationOverflow,
+    );
+}
+
+#[test]
+fn test_negative_amount_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+
+    assert_token_error(token.try_mint(&vec![&e, admin.clone()], &user1, &-1), TokenError::NegativeAmount);
+    assert_token_error(token.try_transfer(&user1, &user2, &-1), TokenError::NegativeAmount);
+    assert_token_error(token.try_burn(&user1, &-1), TokenError::NegativeAmount);
+}
+
+#[test]
+fn test_insufficient_balance_and_allowance_fail() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let owner = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let recipient = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+
+    token.mint(&vec![&e, admin.clone()], &owner, &100);
+    assert_token_error(
+        token.try_transfer(&owner, &recipient, &101),
+        TokenError::InsufficientBalance,
+    );
+
+    let current_ledger = e.ledger().sequence();
+    token.approve(&owner, &spender, &50, &(current_ledger + 100));
+    assert_token_error(
+        token.try_transfer_from(&spender, &owner, &recipient, &51),
+        TokenError::InsufficientAllowance,
+    );
+
+    jump_ledgers(&e, 10);
+    assert_token_error(
+        token.try_approve(&owner, &spender, &50, &(current_ledger + 5)),
+        TokenError::InvalidExpirationLedger,
+    );
+}
+
+#[test]
+fn test_freeze_records_do_not_grow_instance_storage() {
+    // Snapshot capture is disabled: thousands of ledger entries would be
+    // written to disk for no benefit.
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    e.cost_estimate().budget().reset_unlimited();
+
+    let instance_entries = || e.as_contract(&token.address, || e.storage().instance().all().len());
+    let entries_before = instance_entries();
+
+    let reason = String::from_str(&e, "toplu dondurma");
+    for _ in 0..1000 {
+        let account = Address::generate(&e);
+        token.freeze_account(&vec![&e, admin.clone()], &account, &0, &reason, &ComplianceStatus::Sanctioned, &FreezeScope::Outbound);
+    }
+
+    // The frozen-account index adds a single counter to instance storage.
+    assert_eq!(instance_entries(), entries_before + 1);
+    // Per account: the freeze record, one history entry, the history length
+    // and the two frozen-account index entries.
+    let persistent_entries = e.as_contract(&token.address, || e.storage().persistent().all().len());
+    assert_eq!(persistent_entries, 5000);
+}
+
+#[test]
+fn test_migrate_legacy_instance_freeze_records() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let legacy_user = Address::generate(&e);
+    let other_user = Address::generate(&e);
+    let recipient = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    token.mint(&vec![&e, admin.clone()], &legacy_user, &1000);
+
+    let key = DataKey::AccountFreezeInfo(legacy_user.clone());
+    let legacy_info = LegacyFreezeInfo {
+        expiration_ledger: 0,
+        reason: String::from_str(&e, "eski kayıt"),
+        compliance_tag: ComplianceStatus::AmlFlagged,
+    };
+    let migrated_info = FreezeInfo {
+        start_ledger: 0,
+        expiration_ledger: 0,
+        expires_at: 0,
+        reason: legacy_info.reason.clone(),
+        compliance_tag: ComplianceStatus::AmlFlagged,
+        scope: FreezeScope::Outbound,
+    };
+    e.as_contract(&token.address, || {
+        e.storage().instance().set(&key, &legacy_info);
+    });
+
+    // Legacy records are still honoured before migration.
+    assert!(token.is_frozen(&legacy_user));
+    assert_token_error(
+        token.try_transfer(&legacy_user, &recipient, &100),
+        TokenError::AccountFrozen,
+    );
+
+    let migrated = token.migrate_freeze_records(&vec![&e, admin.clone()], &vec![&e, legacy_user.clone(), other_user.clone()]);
+    assert_eq!(migrated, 1);
+
+    e.as_contract(&token.address, || {
+        assert!(!e.storage().instance().has(&key));
+        assert_eq!(
+            e.storage().persistent().get::<DataKey, FreezeInfo>(&key),
+            Some(migrated_info.clone())
+        );
+        assert!(e.storage().persistent().get_ttl(&key) > 0);
+    });
+    assert_eq!(token.get_account_freeze_details(&legacy_user), Some(migrated_info));
+
+    // Running the migration again is a no-op.
+    assert_eq!(token.migrate_freeze_records(&vec![&e, admin.clone()], &vec![&e, legacy_user.clone()]), 0);
+
+    token.unfreeze_account(&vec![&e, admin.clone()], &legacy_user);
+    token.transfer(&legacy_user, &recipient, &100);
+    assert_eq!(token.balance(&recipient), 100);
+}
+
+#[test]
+fn test_inbound_freeze_blocks_receiving_only() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let sanctioned = Address::generate(&e);
+    let sender = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+
+    token.mint(&vec![&e, admin.clone()], &sanctioned, &1000);
+    token.mint(&vec![&e, admin.clone()], &sender, &1000);
     token.freeze_account(
-        &user_a,
-        &u32::MAX,
-        &String::from_str(&e, "Taşma Testi"),
-        &ComplianceStatus::None,
+        &vec![&e, admin.clone()],
+        &sanctioned,
+        &0,
+        &String::from_str(&e, "yaptırım listesi"),
+        &ComplianceStatus::Sanctioned,
+        &FreezeScope::Inbound,
+    );
+    assert!(token.is_frozen(&sanctioned));
+
+    assert_token_error(token.try_mint(&vec![&e, admin.clone()], &sanctioned, &10), TokenError::RecipientFrozen);
+    assert_token_error(
+        token.try_transfer(&sender, &sanctioned, &10),
+        TokenError::RecipientFrozen,
+    );
+    let current_ledger = e.ledger().sequence();
+    token.approve(&sender, &spender, &100, &(current_ledger + 100));
+    assert_token_error(
+        token.try_transfer_from(&spender, &sender, &sanctioned, &10),
+        TokenError::RecipientFrozen,
+    );
+
+    // Sending out is still allowed.
+    token.transfer(&sanctioned, &sender, &100);
+    assert_eq!(token.balance(&sanctioned), 900);
+    assert_eq!(token.balance(&sender), 1100);
+}
+
+#[test]
+fn test_outbound_freeze_still_receives() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let pending = Address::generate(&e);
+    let sender = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+
+    token.mint(&vec![&e, admin.clone()], &sender, &1000);
+    token.freeze_account(
+        &vec![&e, admin.clone()],
+        &pending,
+        &0,
+        &String::from_str(&e, "KYC bekleniyor"),
+        &ComplianceStatus::KycPending,
+        &FreezeScope::Outbound,
+    );
+
+    token.mint(&vec![&e, admin.clone()], &pending, &50);
+    token.transfer(&sender, &pending, &100);
+    assert_eq!(token.balance(&pending), 150);
+    assert_token_error(
+        token.try_transfer(&pending, &sender, &10),
+        TokenError::AccountFrozen,
+    );
+}
+
+#[test]
+fn test_full_freeze_blocks_both_directions() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let frozen = Address::generate(&e);
+    let other = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+
+    token.mint(&vec![&e, admin.clone()], &frozen, &1000);
+    token.mint(&vec![&e, admin.clone()], &other, &1000);
+    token.freeze_account(
+        &vec![&e, admin.clone()],
+        &frozen,
+        &10,
+        &String::from_str(&e, "tam dondurma"),
+        &ComplianceStatus::Sanctioned,
+        &FreezeScope::Full,
+    );
+
+    assert_token_error(token.try_transfer(&frozen, &other, &10), TokenError::AccountFrozen);
+    assert_token_error(token.try_transfer(&other, &frozen, &10), TokenError::RecipientFrozen);
+    assert_token_error(token.try_mint(&vec![&e, admin.clone()], &frozen, &10), TokenError::RecipientFrozen);
+    assert_token_error(token.try_burn(&frozen, &10), TokenError::AccountFrozen);
+
+    jump_ledgers(&e, 10);
+    token.transfer(&other, &frozen, &10);
+    token.transfer(&frozen, &other, &20);
+    assert_eq!(token.balance(&frozen), 990);
+}
+
+#[test]
+fn test_partial_freeze_locks_amount() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let holder = Address::generate(&e);
+    let recipient = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+
+    token.mint(&vec![&e, admin.clone()], &holder, &1_000_000);
+    let dispute_lock = token.freeze_amount(
+        &vec![&e, admin.clone()],
+        &holder,
+        &5_000,
+        &0,
+        &String::from_str(&e, "mahkeme kararı"),
+        &ComplianceStatus::Other,
+    );
+    let review_lock = token.freeze_amount(
+        &vec![&e, admin.clone()],
+        &holder,
+        &10_000,
+        &100,
+        &String::from_str(&e, "AML incelemesi"),
+        &ComplianceStatus::AmlFlagged,
+    );
+    assert_ne!(dispute_lock, review_lock);
+
+    assert!(!token.is_frozen(&holder));
+    assert_eq!(token.balance(&holder), 1_000_000);
+    assert_eq!(token.locked_balance(&holder), 15_000);
+    assert_eq!(token.spendable_balance(&holder), 985_000);
+    assert_eq!(token.get_amount_locks(&holder).len(), 2);
+
+    assert_token_error(
+        token.try_transfer(&holder, &recipient, &985_001),
+        TokenError::BalanceLocked,
+    );
+    assert_token_error(token.try_burn(&holder, &990_000), TokenError::BalanceLocked);
+    token.transfer(&holder, &recipient, &985_000);
+    assert_eq!(token.spendable_balance(&holder), 0);
+
+    // The timed lock lapses on its own; the indefinite one stays.
+    jump_ledgers(&e, 100);
+    assert_eq!(token.locked_balance(&holder), 5_000);
+    assert_eq!(token.spendable_balance(&holder), 10_000);
+    let locks = token.get_amount_locks(&holder);
+    assert_eq!(locks.len(), 1);
+    assert_eq!(locks.get(0).unwrap().id, dispute_lock);
+    assert_eq!(locks.get(0).unwrap().reason, String::from_str(&e, "mahkeme kararı"));
+
+    token.release_amount(&vec![&e, admin.clone()], &holder, &dispute_lock);
+    assert_eq!(token.locked_balance(&holder), 0);
+    token.transfer(&holder, &recipient, &15_000);
+    assert_eq!(token.balance(&holder), 0);
+
+    assert_token_error(
+        token.try_release_amount(&vec![&e, admin.clone()], &holder, &dispute_lock),
+        TokenError::LockNotFound,
+    );
+}
+
+#[test]
+fn test_partial_freeze_limits() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let holder = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    let reason = String::from_str(&e, "limit");
+
+    assert_token_error(
+        token.try_freeze_amount(&vec![&e, admin.clone()], &holder, &0, &0, &reason, &ComplianceStatus::None),
+        TokenError::InvalidLockAmount,
+    );
+    for _ in 0..20 {
+        token.freeze_amount(&vec![&e, admin.clone()], &holder, &1, &10, &reason, &ComplianceStatus::None);
+    }
+    assert_token_error(
+        token.try_freeze_amount(&vec![&e, admin.clone()], &holder, &1, &10, &reason, &ComplianceStatus::None),
+        TokenError::TooManyLocks,
+    );
+
+    // Expired locks free up their slots.
+    jump_ledgers(&e, 10);
+    token.freeze_amount(&vec![&e, admin.clone()], &holder, &1, &0, &reason, &ComplianceStatus::None);
+    assert_eq!(token.get_amount_locks(&holder).len(), 1);
+}
+
+#[test]
+fn test_roles_segregate_minting_and_freezing() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let minter = Address::generate(&e);
+    let freezer = Address::generate(&e);
+    let user = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    let reason = String::from_str(&e, "rol testi");
+
+    token.grant_role(&admin, &Role::Minter, &minter);
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "role_granted"), Role::Minter, minter.clone()).into_val(&e),
+        admin.into_val(&e),
+    );
+    token.grant_role(&admin, &Role::Freezer, &freezer);
+    assert!(token.has_role(&Role::Minter, &minter));
+    assert!(!token.has_role(&Role::Freezer, &minter));
+    assert_eq!(token.get_role_members(&Role::Freezer), vec![&e, freezer.clone()]);
+
+    token.mint(&vec![&e, minter.clone()], &user, &100);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            minter.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "mint"),
+                    (vec![&e, minter.clone()], user.clone(), 100_i128).into_val(&e),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_eq!(token.balance(&user), 100);
+
+    assert_token_error(
+        token.try_freeze_account(&vec![&e, minter.clone()], &user, &0, &reason, &ComplianceStatus::None, &FreezeScope::Full),
+        TokenError::MissingRole,
+    );
+    assert_token_error(token.try_mint(&vec![&e, freezer.clone()], &user, &100), TokenError::MissingRole);
+    assert_token_error(
+        token.try_grant_role(&freezer, &Role::Minter, &freezer),
+        TokenError::MissingRole,
+    );
+
+    token.freeze_account(&vec![&e, freezer.clone()], &user, &0, &reason, &ComplianceStatus::None, &FreezeScope::Full);
+    assert!(token.is_frozen(&user));
+    token.unfreeze_account(&vec![&e, freezer.clone()], &user);
+    assert!(!token.is_frozen(&user));
+
+    token.revoke_role(&admin, &Role::Minter, &minter);
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "role_revoked"), Role::Minter, minter.clone()).into_val(&e),
+        admin.into_val(&e),
+    );
+    assert_token_error(token.try_mint(&vec![&e, minter.clone()], &user, &100), TokenError::MissingRole);
+    assert_token_error(
+        token.try_revoke_role(&admin, &Role::Minter, &minter),
+        TokenError::RoleNotGranted,
+    );
+}
+
+#[test]
+fn test_role_admin_and_renounce() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let role_admin = Address::generate(&e);
+    let officer = Address::generate(&e);
+    let user = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+
+    token.grant_role(&admin, &Role::AccessManager, &role_admin);
+    token.grant_role(&role_admin, &Role::ComplianceOfficer, &officer);
+    assert_token_error(
+        token.try_grant_role(&role_admin, &Role::ComplianceOfficer, &officer),
+        TokenError::RoleAlreadyGranted,
+    );
+
+    assert_token_error(
+        token.try_set_compliance_tag(&vec![&e, officer.clone()], &user, &ComplianceStatus::Sanctioned),
+        TokenError::AccountNotFrozen,
+    );
+    token.freeze_account(
+        &vec![&e, admin.clone()],
+        &user,
+        &0,
+        &String::from_str(&e, "inceleme"),
+        &ComplianceStatus::KycPending,
+        &FreezeScope::Outbound,
+    );
+    let old_info = token.get_account_freeze_details(&user).unwrap();
+    let mut new_info = old_info.clone();
+    new_info.compliance_tag = ComplianceStatus::Sanctioned;
+    token.set_compliance_tag(&vec![&e, officer.clone()], &user, &ComplianceStatus::Sanctioned);
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "freeze_amended"), user.clone()).into_val(&e),
+        (officer.clone(), old_info, new_info).into_val(&e),
+    );
+    assert_eq!(token.get_compliance_tag_for_account(&user), ComplianceStatus::Sanctioned);
+    let history = token.get_freeze_history(&user, &0, &10);
+    assert_eq!(history.len(), 2);
+    let now = e.ledger().sequence();
+    assert_eq!(history.get(0).unwrap().end, FreezeEnd::Amended(officer.clone(), now));
+    let amendment = history.get(1).unwrap();
+    assert_eq!(amendment.frozen_by, officer);
+    assert_eq!(amendment.compliance_tag, ComplianceStatus::Sanctioned);
+    assert_eq!(amendment.end, FreezeEnd::Open);
+
+    token.renounce_role(&officer, &Role::ComplianceOfficer);
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "role_renounced"), Role::ComplianceOfficer, officer.clone()).into_val(&e),
+        ().into_val(&e),
+    );
+    assert!(token.get_role_members(&Role::ComplianceOfficer).is_empty());
+    assert_token_error(
+        token.try_set_compliance_tag(&vec![&e, officer.clone()], &user, &ComplianceStatus::None),
+        TokenError::MissingRole,
     );
-}
\ No newline at end of file
+}
+
+#[test]
+#[should_panic]
+fn test_role_holder_must_authorize() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let minter = Address::generate(&e);
+    let user = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    token.grant_role(&admin, &Role::Minter, &minter);
+
+    e.set_auths(&[]);
+    token.mint(&vec![&e, minter.clone()], &user, &100);
+}
+
+#[test]
+fn test_two_step_admin_transfer_with_delay() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let new_admin = Address::generate(&e);
+    let user = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+
+    assert_token_error(token.try_accept_admin(), TokenError::NoPendingAdmin);
+
+    token.propose_admin(&vec![&e, admin.clone()], &new_admin, &100);
+    let accept_after = e.ledger().sequence() + 100;
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "admin_proposed"), vec![&e, admin.clone()], new_admin.clone()).into_val(&e),
+        accept_after.into_val(&e),
+    );
+    let pending = token.pending_admin().unwrap();
+    assert_eq!(pending.new_admin, new_admin);
+    assert_eq!(pending.accept_after_ledger, accept_after);
+
+    // Nothing changes until the new admin accepts.
+    assert_eq!(token.admin().signers, vec![&e, admin.clone()]);
+    token.mint(&vec![&e, admin.clone()], &user, &10);
+    assert_token_error(token.try_mint(&vec![&e, new_admin.clone()], &user, &10), TokenError::MissingRole);
+
+    jump_ledgers(&e, 99);
+    assert_token_error(token.try_accept_admin(), TokenError::AdminDelayNotElapsed);
+
+    jump_ledgers(&e, 1);
+    token.accept_admin();
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            new_admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "accept_admin"),
+                    ().into_val(&e),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "set_admin"), vec![&e, admin.clone()], new_admin.clone()).into_val(&e),
+        ().into_val(&e),
+    );
+    assert_eq!(token.admin().signers, vec![&e, new_admin.clone()]);
+    assert!(token.pending_admin().is_none());
+    token.mint(&vec![&e, new_admin.clone()], &user, &10);
+    assert_token_error(token.try_mint(&vec![&e, admin.clone()], &user, &10), TokenError::MissingRole);
+}
+
+#[test]
+fn test_set_admin_requires_acceptance_and_can_be_cancelled() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let mistyped = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+
+    token.set_admin(&vec![&e, admin.clone()], &mistyped);
+    assert_eq!(token.admin().signers, vec![&e, admin.clone()]);
+    assert_eq!(token.pending_admin().unwrap().new_admin, mistyped);
+
+    token.cancel_admin_proposal(&vec![&e, admin.clone()]);
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "admin_cancelled"), vec![&e, admin.clone()], mistyped.clone()).into_val(&e),
+        ().into_val(&e),
+    );
+    assert!(token.pending_admin().is_none());
+    assert_token_error(token.try_accept_admin(), TokenError::NoPendingAdmin);
+    assert_token_error(token.try_cancel_admin_proposal(&vec![&e, admin.clone()]), TokenError::NoPendingAdmin);
+    assert_eq!(token.admin().signers, vec![&e, admin.clone()]);
+}
+
+#[test]
+fn test_total_supply_tracks_mint_and_burn() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let owner = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+
+    assert_eq!(token.total_supply(), 0);
+    token.mint(&vec![&e, admin.clone()], &owner, &1000);
+    assert_eq!(token.total_supply(), 1000);
+    token.burn(&owner, &100);
+    assert_eq!(token.total_supply(), 900);
+
+    let current_ledger = e.ledger().sequence();
+    token.approve(&owner, &spender, &200, &(current_ledger + 100));
+    token.burn_from(&spender, &owner, &200);
+    assert_eq!(token.total_supply(), 700);
+
+    // Failed operations leave the supply untouched.
+    assert_token_error(token.try_burn(&owner, &701), TokenError::InsufficientBalance);
+    assert_eq!(token.total_supply(), 700);
+
+    assert_token_error(
+        token.try_mint(&vec![&e, admin.clone()], &owner, &(i128::MAX - 699)),
+        TokenError::SupplyOverflow,
+    );
+    assert_eq!(token.total_supply(), 700);
+}
+
+#[test]
+fn test_total_supply_matches_sum_of_balances() {
+    let e = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    e.mock_all_auths();
+    e.cost_estimate().budget().reset_unlimited();
+    let admin = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    let accounts: std::vec::Vec<Address> = (0..5).map(|_| Address::generate(&e)).collect();
+    let expiration_ledger = e.ledger().sequence() + 10_000;
+
+    // Deterministic linear congruential generator so failures are reproducible.
+    let mut seed: u64 = 0x5eed_1234;
+    let mut next = |bound: u64| {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (seed >> 33) % bound
+    };
+
+    for _ in 0..200 {
+        let a = &accounts[next(5) as usize];
+        let b = &accounts[next(5) as usize];
+        let amount = next(500) as i128;
+        // Operations are allowed to fail; the invariant must hold either way.
+        match next(5) {
+            0 => {
+                let _ = token.try_mint(&vec![&e, admin.clone()], a, &amount);
+            }
+            1 => {
+                let _ = token.try_transfer(a, b, &amount);
+            }
+            2 => {
+                let _ = token.try_burn(a, &amount);
+            }
+            3 => {
+                token.approve(a, b, &amount, &expiration_ledger);
+                let _ = token.try_burn_from(b, a, &amount);
+            }
+            _ => {
+                token.approve(a, b, &amount, &expiration_ledger);
+                let _ = token.try_transfer_from(b, a, b, &amount);
+            }
+        }
+
+        let sum: i128 = accounts.iter().map(|account| token.balance(account)).sum();
+        assert_eq!(sum, token.total_supply());
+    }
+    assert!(token.total_supply() > 0);
+}
+
+#[test]
+fn test_max_supply_cap_enforced_on_mint() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let user = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+
+    assert_eq!(token.max_supply(), None);
+    assert_token_error(token.try_update_max_supply(&vec![&e, admin.clone()], &500), TokenError::SupplyCapNotSet);
+    token.mint(&vec![&e, admin.clone()], &user, &400);
+
+    assert_token_error(token.try_set_max_supply(&vec![&e, admin.clone()], &399, &false), TokenError::InvalidSupplyCap);
+    token.set_max_supply(&vec![&e, admin.clone()], &1000, &false);
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "max_supply_set"), vec![&e, admin.clone()]).into_val(&e),
+        (1000_i128, false).into_val(&e),
+    );
+    assert_eq!(token.max_supply(), Some(1000));
+    assert_token_error(token.try_set_max_supply(&vec![&e, admin.clone()], &2000, &true), TokenError::SupplyCapAlreadySet);
+
+    token.mint(&vec![&e, admin.clone()], &user, &600);
+    assert_token_error(token.try_mint(&vec![&e, admin.clone()], &user, &1), TokenError::MaxSupplyExceeded);
+    assert_eq!(token.total_supply(), 1000);
+
+    // Burning frees room under the cap.
+    token.burn(&user, &100);
+    token.mint(&vec![&e, admin.clone()], &user, &100);
+
+    assert_token_error(
+        token.try_update_max_supply(&vec![&e, admin.clone()], &1001),
+        TokenError::SupplyCapIncreaseNotAllowed,
+    );
+    token.burn(&user, &200);
+    assert_token_error(token.try_update_max_supply(&vec![&e, admin.clone()], &799), TokenError::InvalidSupplyCap);
+    token.update_max_supply(&vec![&e, admin.clone()], &800);
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "max_supply_updated"), vec![&e, admin.clone()]).into_val(&e),
+        (1000_i128, 800_i128).into_val(&e),
+    );
+    assert_eq!(token.max_supply(), Some(800));
+    assert_token_error(token.try_mint(&vec![&e, admin.clone()], &user, &1), TokenError::MaxSupplyExceeded);
+}
+
+#[test]
+fn test_raisable_max_supply_cap() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let user = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+
+    token.set_max_supply(&vec![&e, admin.clone()], &100, &true);
+    token.mint(&vec![&e, admin.clone()], &user, &100);
+    token.update_max_supply(&vec![&e, admin.clone()], &150);
+    token.mint(&vec![&e, admin.clone()], &user, &50);
+    assert_eq!(token.max_supply(), Some(150));
+    assert_eq!(token.total_supply(), 150);
+}
+
+#[test]
+fn test_clawback_from_sanctioned_account() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let sanctioned = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+
+    token.mint(&vec![&e, admin.clone()], &sanctioned, &1000);
+    token.freeze_account(
+        &vec![&e, admin.clone()],
+        &sanctioned,
+        &0,
+        &String::from_str(&e, "OFAC"),
+        &ComplianceStatus::Sanctioned,
+        &FreezeScope::Full,
+    );
+    token.freeze_amount(&vec![&e, admin.clone()], &sanctioned, &300, &0, &String::from_str(&e, "dava"), &ComplianceStatus::Other);
+
+    let reason = String::from_str(&e, "mahkeme kararı 2026/12");
+    token.clawback(&vec![&e, admin.clone()], &sanctioned, &800, &reason);
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "clawback"), vec![&e, admin.clone()], sanctioned.clone()).into_val(&e),
+        (800_i128, reason.clone(), ComplianceStatus::Sanctioned).into_val(&e),
+    );
+    assert_eq!(token.balance(&sanctioned), 200);
+    assert_eq!(token.total_supply(), 200);
+    assert!(token.is_frozen(&sanctioned));
+
+    assert_token_error(
+        token.try_clawback(&vec![&e, admin.clone()], &sanctioned, &201, &reason),
+        TokenError::InsufficientBalance,
+    );
+    assert_token_error(token.try_clawback(&vec![&e, admin.clone()], &sanctioned, &-1, &reason), TokenError::NegativeAmount);
+}
+
+#[test]
+fn test_forced_transfer_to_custody() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let frozen = Address::generate(&e);
+    let custody = Address::generate(&e);
+    let blocked = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+
+    token.mint(&vec![&e, admin.clone()], &frozen, &1000);
+    token.freeze_account(
+        &vec![&e, admin.clone()],
+        &frozen,
+        &0,
+        &String::from_str(&e, "AML"),
+        &ComplianceStatus::AmlFlagged,
+        &FreezeScope::Outbound,
+    );
+    token.freeze_account(
+        &vec![&e, admin.clone()],
+        &blocked,
+        &0,
+        &String::from_str(&e, "yaptırım"),
+        &ComplianceStatus::Sanctioned,
+        &FreezeScope::Inbound,
+    );
+
+    let reason = String::from_str(&e, "emanet hesabına aktarım");
+    assert_token_error(
+        token.try_forced_transfer(&vec![&e, admin.clone()], &frozen, &blocked, &100, &reason),
+        TokenError::RecipientFrozen,
+    );
+    token.forced_transfer(&vec![&e, admin.clone()], &frozen, &custody, &1000, &reason);
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "forced_transfer"), vec![&e, admin.clone()], frozen.clone(), custody.clone())
+            .into_val(&e),
+        (1000_i128, reason, ComplianceStatus::AmlFlagged).into_val(&e),
+    );
+    assert_eq!(token.balance(&frozen), 0);
+    assert_eq!(token.balance(&custody), 1000);
+    assert_eq!(token.total_supply(), 1000);
+}
+
+#[test]
+#[should_panic]
+fn test_clawback_requires_admin() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let user = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    token.mint(&vec![&e, admin.clone()], &user, &100);
+
+    e.set_auths(&[]);
+    token.clawback(&vec![&e, admin.clone()], &user, &100, &String::from_str(&e, "yetkisiz"));
+}
+
+#[test]
+fn test_pause_blocks_all_token_movement() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let guardian = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+
+    token.mint(&vec![&e, admin.clone()], &user1, &1000);
+    let expiration_ledger = e.ledger().sequence() + 100;
+    token.approve(&user1, &user2, &500, &expiration_ledger);
+
+    token.grant_role(&admin, &Role::Guardian, &guardian);
+    assert_token_error(token.try_unpause(&guardian), TokenError::NotPaused);
+    let reason = String::from_str(&e, "olay müdahalesi");
+    token.pause(&guardian, &reason, &0);
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "paused"), guardian.clone()).into_val(&e),
+        (reason.clone(), 0_u32).into_val(&e),
+    );
+    assert!(token.is_paused());
+    assert_eq!(token.get_pause_info().unwrap().paused_by, guardian);
+
+    assert_token_error(token.try_transfer(&user1, &user2, &1), TokenError::ContractPaused);
+    assert_token_error(
+        token.try_transfer_from(&user2, &user1, &user2, &1),
+        TokenError::ContractPaused,
+    );
+    assert_token_error(token.try_burn(&user1, &1), TokenError::ContractPaused);
+    assert_token_error(token.try_burn_from(&user2, &user1, &1), TokenError::ContractPaused);
+    assert_token_error(
+        token.try_approve(&user1, &user2, &1, &expiration_ledger),
+        TokenError::ContractPaused,
+    );
+    assert_token_error(token.try_mint(&vec![&e, admin.clone()], &user1, &1), TokenError::ContractPaused);
+    // Read-only queries keep working.
+    assert_eq!(token.balance(&user1), 1000);
+
+    assert_token_error(token.try_pause(&user1, &reason, &0), TokenError::MissingRole);
+    token.unpause(&guardian);
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "unpaused"), guardian.clone()).into_val(&e),
+        ().into_val(&e),
+    );
+    assert!(!token.is_paused());
+    token.transfer(&user1, &user2, &1);
+    assert_eq!(token.balance(&user2), 1);
+}
+
+#[test]
+fn test_timed_pause_expires() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    token.mint(&vec![&e, admin.clone()], &user1, &1000);
+
+    token.pause(&admin, &String::from_str(&e, "bakım"), &10);
+    assert_eq!(token.get_pause_info().unwrap().expiration_ledger, e.ledger().sequence() + 10);
+
+    jump_ledgers(&e, 9);
+    assert!(token.is_paused());
+    assert_token_error(token.try_transfer(&user1, &user2, &1), TokenError::ContractPaused);
+
+    jump_ledgers(&e, 1);
+    assert!(!token.is_paused());
+    assert!(token.get_pause_info().is_none());
+    token.transfer(&user1, &user2, &1);
+    assert_token_error(token.try_unpause(&admin), TokenError::NotPaused);
+    assert_token_error(
+        token.try_pause(&admin, &String::from_str(&e, "bakım"), &u32::MAX),
+        TokenError::DurationOverflow,
+    );
+}
+
+#[test]
+fn test_upgrade_between_builds_preserves_state() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let user = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    token.mint(&vec![&e, admin.clone()], &user, &1000);
+    assert_eq!(token.version(), CURRENT_SCHEMA_VERSION);
+
+    let token_hash = e.deployer().upload_contract_wasm(WASM_BYTES);
+    let fixture_hash = e.deployer().upload_contract_wasm(UPGRADE_FIXTURE_WASM_BYTES);
+
+    token.upgrade(&vec![&e, admin.clone()], &fixture_hash);
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "upgraded"), vec![&e, admin.clone()]).into_val(&e),
+        fixture_hash.clone().into_val(&e),
+    );
+    let version: u32 = e.invoke_contract(&token.address, &Symbol::new(&e, "version"), vec![&e]);
+    assert_eq!(version, 99);
+    let balance: i128 = e.invoke_contract(
+        &token.address,
+        &Symbol::new(&e, "balance"),
+        vec![&e, user.into_val(&e)],
+    );
+    assert_eq!(balance, 1000);
+
+    let _: () = e.invoke_contract(
+        &token.address,
+        &Symbol::new(&e, "upgrade"),
+        vec![&e, token_hash.into_val(&e)],
+    );
+    assert_eq!(token.version(), CURRENT_SCHEMA_VERSION);
+    assert_eq!(token.migrate(&vec![&e, admin.clone()], &None), CURRENT_SCHEMA_VERSION);
+    assert_eq!(token.balance(&user), 1000);
+    token.mint(&vec![&e, admin.clone()], &user, &1);
+    assert_eq!(token.total_supply(), 1001);
+}
+
+#[test]
+fn test_migrate_from_unversioned_schema_is_idempotent() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+
+    // Simulate a deployment made before schema versioning existed.
+    e.as_contract(&token.address, || {
+        e.storage().instance().remove(&DataKey::SchemaVersion);
+        e.storage().instance().remove(&DataKey::TotalSupply);
+    });
+    assert_eq!(token.version(), 1);
+
+    assert_eq!(token.migrate(&vec![&e, admin.clone()], &Some(0)), CURRENT_SCHEMA_VERSION);
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "migrated"), vec![&e, admin.clone()]).into_val(&e),
+        (1_u32, CURRENT_SCHEMA_VERSION).into_val(&e),
+    );
+    assert_eq!(token.version(), CURRENT_SCHEMA_VERSION);
+    e.as_contract(&token.address, || {
+        assert_eq!(e.storage().instance().get::<_, i128>(&DataKey::TotalSupply), Some(0));
+    });
+
+    assert_eq!(token.migrate(&vec![&e, admin.clone()], &None), CURRENT_SCHEMA_VERSION);
+    assert_eq!(token.version(), CURRENT_SCHEMA_VERSION);
+}
+
+#[test]
+fn test_migrate_seeds_supply_of_live_deployment() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let user = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    token.mint(&vec![&e, admin.clone()], &user, &1000);
+
+    // Simulate a pre-versioning deployment that already holds balances.
+    e.as_contract(&token.address, || {
+        e.storage().instance().remove(&DataKey::SchemaVersion);
+        e.storage().instance().remove(&DataKey::TotalSupply);
+    });
+
+    assert_token_error(
+        token.try_migrate(&vec![&e, admin.clone()], &None),
+        TokenError::LegacySupplyRequired,
+    );
+    assert_token_error(
+        token.try_migrate(&vec![&e, admin.clone()], &Some(-1)),
+        TokenError::NegativeAmount,
+    );
+    assert_eq!(token.migrate(&vec![&e, admin.clone()], &Some(1000)), CURRENT_SCHEMA_VERSION);
+    assert_eq!(token.total_supply(), 1000);
+
+    // Once tracked, the attested supply is ignored.
+    assert_eq!(token.migrate(&vec![&e, admin.clone()], &Some(5)), CURRENT_SCHEMA_VERSION);
+    assert_eq!(token.total_supply(), 1000);
+
+    token.burn(&user, &1);
+    token.clawback(&vec![&e, admin.clone()], &user, &100, &String::from_str(&e, "court order"));
+    assert_eq!(token.balance(&user), 899);
+    assert_eq!(token.total_supply(), 899);
+}
+
+#[test]
+#[should_panic]
+fn test_upgrade_requires_admin() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    let fixture_hash = e.deployer().upload_contract_wasm(UPGRADE_FIXTURE_WASM_BYTES);
+
+    e.set_auths(&[]);
+    token.upgrade(&vec![&e, admin.clone()], &fixture_hash);
+}
+
+#[test]
+fn test_freeze_history_records_freeze_lifecycle() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let freezer = Address::generate(&e);
+    let user = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    token.grant_role(&admin, &Role::Freezer, &freezer);
+
+    let start = e.ledger().sequence();
+    token.freeze_account(&vec![&e, freezer.clone()], &user, &0, &String::from_str(&e, "AML incelemesi"), &ComplianceStatus::AmlFlagged, &FreezeScope::Full);
+    jump_ledgers(&e, 5);
+    token.unfreeze_account(&vec![&e, admin.clone()], &user);
+    token.freeze_account(&vec![&e, freezer.clone()], &user, &10, &String::from_str(&e, "KYC eksik"), &ComplianceStatus::KycPending, &FreezeScope::Outbound);
+    token.freeze_account(&vec![&e, admin.clone()], &user, &20, &String::from_str(&e, "KYC eksik, uzatıldı"), &ComplianceStatus::KycPending, &FreezeScope::Outbound);
+    jump_ledgers(&e, 20);
+
+    assert_eq!(token.get_freeze_history_len(&user), 3);
+    let history = token.get_freeze_history(&user, &0, &10);
+    assert_eq!(history.len(), 3);
+
+    let first = history.get(0).unwrap();
+    assert_eq!(first.frozen_by, freezer);
+    assert_eq!(first.frozen_at_ledger, start);
+    assert_eq!(first.expiration_ledger, 0);
+    assert_eq!(first.reason, String::from_str(&e, "AML incelemesi"));
+    assert_eq!(first.compliance_tag, ComplianceStatus::AmlFlagged);
+    assert_eq!(first.scope, FreezeScope::Full);
+    assert_eq!(first.end, FreezeEnd::Unfrozen(admin.clone(), start + 5));
+
+    let second = history.get(1).unwrap();
+    assert_eq!(second.expiration_ledger, start + 15);
+    assert_eq!(second.end, FreezeEnd::Replaced(admin.clone(), start + 5));
+
+    // The last freeze was never lifted; it is reported as expired once it lapses.
+    let third = history.get(2).unwrap();
+    assert_eq!(third.frozen_by, admin);
+    assert_eq!(third.end, FreezeEnd::Expired(start + 25));
+}
+
+#[test]
+fn test_freeze_history_is_paginated_and_bounded() {
+    let e = Env::default();
+    e.mock_all_auths();
+    e.cost_estimate().budget().reset_unlimited();
+    let admin = Address::generate(&e);
+    let user = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+
+    let total = MAX_FREEZE_HISTORY + 5;
+    for i in 0..total {
+        token.freeze_account(&vec![&e, admin.clone()], &user, &(i + 1), &String::from_str(&e, "test"), &ComplianceStatus::Other, &FreezeScope::Outbound);
+        token.unfreeze_account(&vec![&e, admin.clone()], &user);
+    }
+    assert_eq!(token.get_freeze_history_len(&user), total);
+
+    let page = token.get_freeze_history(&user, &10, &3);
+    assert_eq!(page.len(), 3);
+    assert_eq!(page.get(0).unwrap().expiration_ledger, e.ledger().sequence() + 11);
+
+    // Only the most recent MAX_FREEZE_HISTORY entries are retained.
+    let oldest = token.get_freeze_history(&user, &0, &1).get(0).unwrap();
+    assert_eq!(oldest.expiration_ledger, e.ledger().sequence() + 6);
+    assert_eq!(token.get_freeze_history(&user, &0, &u32::MAX).len(), MAX_FREEZE_HISTORY);
+    assert_eq!(token.get_freeze_history(&user, &(total - 2), &10).len(), 2);
+    assert_eq!(token.get_freeze_history(&user, &total, &10).len(), 0);
+}
+
+#[test]
+fn test_list_frozen_accounts() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let sanctioned = Address::generate(&e);
+    let flagged = Address::generate(&e);
+    let pending = Address::generate(&e);
+    let timed = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+
+    let reason = String::from_str(&e, "uyum kontrolü");
+    token.freeze_account(&vec![&e, admin.clone()], &sanctioned, &0, &reason, &ComplianceStatus::Sanctioned, &FreezeScope::Full);
+    token.freeze_account(&vec![&e, admin.clone()], &flagged, &0, &reason, &ComplianceStatus::AmlFlagged, &FreezeScope::Outbound);
+    token.freeze_account(&vec![&e, admin.clone()], &pending, &0, &reason, &ComplianceStatus::KycPending, &FreezeScope::Inbound);
+    token.freeze_account(&vec![&e, admin.clone()], &timed, &10, &reason, &ComplianceStatus::Sanctioned, &FreezeScope::Outbound);
+
+    let listed = |page: crate::storage_types::FrozenAccountsPage| {
+        let mut accounts = std::vec::Vec::new();
+        for entry in page.accounts.iter() {
+            assert_eq!(Some(entry.info.clone()), token.get_account_freeze_details(&entry.account));
+            accounts.push(entry.account);
+        }
+        accounts
+    };
+
+    let all = token.list_frozen_accounts(&0, &10, &None);
+    assert_eq!(all.next_cursor, None);
+    assert_eq!(listed(all), [sanctioned.clone(), flagged.clone(), pending.clone(), timed.clone()]);
+
+    let sanctioned_only = token.list_frozen_accounts(&0, &10, &Some(ComplianceStatus::Sanctioned));
+    assert_eq!(listed(sanctioned_only), [sanctioned.clone(), timed.clone()]);
+
+    let first_page = token.list_frozen_accounts(&0, &3, &None);
+    assert_eq!(first_page.next_cursor, Some(3));
+    assert_eq!(first_page.accounts.len(), 3);
+    let second_page = token.list_frozen_accounts(&3, &3, &None);
+    assert_eq!(second_page.next_cursor, None);
+    assert_eq!(listed(second_page), std::vec![timed.clone()]);
+
+    // Unfreezing moves the last account into the freed slot.
+    token.unfreeze_account(&vec![&e, admin.clone()], &flagged);
+    assert_eq!(
+        listed(token.list_frozen_accounts(&0, &10, &None)),
+        [sanctioned.clone(), timed.clone(), pending.clone()]
+    );
+
+    // A lapsed timed freeze is no longer listed, and re-freezing lists it again.
+    jump_ledgers(&e, 10);
+    assert_eq!(
+        listed(token.list_frozen_accounts(&0, &10, &None)),
+        [sanctioned.clone(), pending.clone()]
+    );
+    token.freeze_account(&vec![&e, admin.clone()], &timed, &0, &reason, &ComplianceStatus::Other, &FreezeScope::Full);
+    assert_eq!(
+        listed(token.list_frozen_accounts(&0, &10, &Some(ComplianceStatus::Other))),
+        std::vec![timed.clone()]
+    );
+
+    token.unfreeze_account(&vec![&e, admin.clone()], &sanctioned);
+    token.unfreeze_account(&vec![&e, admin.clone()], &pending);
+    token.unfreeze_account(&vec![&e, admin.clone()], &timed);
+    let empty = token.list_frozen_accounts(&0, &10, &None);
+    assert_eq!(empty.accounts.len(), 0);
+    assert_eq!(empty.next_cursor, None);
+}
+
+#[test]
+fn test_prune_lapsed_freezes_from_index() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let indefinite = Address::generate(&e);
+    let scheduled = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+
+    let start = e.ledger().sequence();
+    let reason = String::from_str(&e, "süreli inceleme");
+    token.freeze_account(&vec![&e, admin.clone()], &indefinite, &0, &reason, &ComplianceStatus::Sanctioned, &FreezeScope::Full);
+    let mut timed = std::vec::Vec::new();
+    for _ in 0..6 {
+        let account = Address::generate(&e);
+        token.freeze_account(&vec![&e, admin.clone()], &account, &10, &reason, &ComplianceStatus::AmlFlagged, &FreezeScope::Outbound);
+        timed.push(account);
+    }
+    token.schedule_freeze(
+        &vec![&e, admin.clone()],
+        &scheduled,
+        &FreezeInfo {
+            start_ledger: start + 20,
+            expiration_ledger: 0,
+            expires_at: 0,
+            reason: reason.clone(),
+            compliance_tag: ComplianceStatus::Sanctioned,
+            scope: FreezeScope::Full,
+        },
+    );
+    jump_ledgers(&e, 10);
+
+    // Lapsed freezes still take up slots, so pages come back short.
+    let page = token.list_frozen_accounts(&0, &4, &None);
+    assert_eq!(page.accounts.len(), 1);
+    assert_eq!(page.next_cursor, Some(4));
+    let page = token.list_frozen_accounts(&4, &4, &None);
+    assert_eq!(page.accounts.len(), 0);
+    assert_eq!(page.next_cursor, None);
+
+    // Each call deletes at most a batch of records and says where to resume.
+    let mut cursor = token.prune_frozen_index(&0, &50);
+    assert!(cursor.is_some());
+    assert!(e.cost_estimate().resources().write_entries <= TX_MAX_WRITE_ENTRIES);
+    while let Some(next) = cursor {
+        cursor = token.prune_frozen_index(&next, &50);
+        assert!(e.cost_estimate().resources().write_entries <= TX_MAX_WRITE_ENTRIES);
+    }
+
+    let page = token.list_frozen_accounts(&0, &2, &None);
+    assert_eq!(page.accounts.len(), 1);
+    assert_eq!(page.accounts.get(0).unwrap().account, indefinite);
+    assert_eq!(page.next_cursor, None);
+    let pending = token.list_pending_freezes(&0, &2, &None);
+    assert_eq!(pending.accounts.get(0).unwrap().account, scheduled);
+    assert_eq!(token.prune_frozen_index(&0, &50), None);
+
+    // The history of a pruned freeze still reports it as expired.
+    for account in timed.iter() {
+        assert!(!token.is_frozen(account));
+        let history = token.get_freeze_history(account, &0, &10);
+        assert_eq!(history.get(0).unwrap().end, FreezeEnd::Expired(start + 10));
+    }
+    jump_ledgers(&e, 10);
+    assert!(token.is_frozen(&scheduled));
+}
+
+#[test]
+fn test_batch_freeze_and_unfreeze() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let freezer = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    token.grant_role(&admin, &Role::Freezer, &freezer);
+
+    let reason = String::from_str(&e, "yaptırım listesi");
+    let mut accounts: Vec<Address> = Vec::new(&e);
+    let mut freezes: Vec<(Address, u32, String, ComplianceStatus)> = Vec::new(&e);
+    for i in 0..MAX_FREEZE_BATCH {
+        let account = Address::generate(&e);
+        accounts.push_back(account.clone());
+        freezes.push_back((account, i % 2 * 100, reason.clone(), ComplianceStatus::Sanctioned));
+    }
+
+    // A full batch stays within the per-transaction write limit and needs a
+    // single authorization.
+    token.freeze_accounts(&vec![&e, freezer.clone()], &freezes, &FreezeScope::Full);
+    assert!(e.cost_estimate().resources().write_entries <= TX_MAX_WRITE_ENTRIES);
+    assert_eq!(e.auths().len(), 1);
+    assert_eq!(e.auths()[0].0, freezer);
+    let freeze_events = count_events(&e, "freeze_acc");
+    assert_eq!(freeze_events, MAX_FREEZE_BATCH as usize);
+
+    for (i, account) in accounts.iter().enumerate() {
+        let info = token.get_account_freeze_details(&account).unwrap();
+        assert_eq!(info.scope, FreezeScope::Full);
+        assert_eq!(info.compliance_tag, ComplianceStatus::Sanctioned);
+        let expected_expiration = if i % 2 == 0 { 0 } else { e.ledger().sequence() + 100 };
+        assert_eq!(info.expiration_ledger, expected_expiration);
+    }
+    let listed = token.list_frozen_accounts(&0, &MAX_FREEZE_BATCH, &None);
+    assert_eq!(listed.accounts.len(), MAX_FREEZE_BATCH);
+
+    // Replacing active freezes also closes their history entries, the most
+    // expensive case, and still fits.
+    token.freeze_accounts(&vec![&e, freezer.clone()], &freezes, &FreezeScope::Outbound);
+    assert!(e.cost_estimate().resources().write_entries <= TX_MAX_WRITE_ENTRIES);
+    assert_eq!(
+        e.cost_estimate().resources().write_entries,
+        MAX_FREEZE_BATCH * FREEZE_WRITE_ENTRIES + 2
+    );
+    for account in accounts.iter() {
+        assert_eq!(token.get_account_freeze_details(&account).unwrap().scope, FreezeScope::Outbound);
+        assert_eq!(token.get_freeze_history_len(&account), 2);
+    }
+
+    // Accounts that are not frozen are skipped without an event.
+    let mut to_unfreeze = accounts.clone();
+    to_unfreeze.pop_back();
+    to_unfreeze.push_back(Address::generate(&e));
+    token.unfreeze_accounts(&vec![&e, freezer.clone()], &to_unfreeze);
+    assert!(e.cost_estimate().resources().write_entries <= TX_MAX_WRITE_ENTRIES);
+    assert_eq!(e.auths().len(), 1);
+    let unfreeze_events = count_events(&e, "unfrz_acc");
+    assert_eq!(unfreeze_events, MAX_FREEZE_BATCH as usize - 1);
+    assert!(!token.is_frozen(&accounts.get(0).unwrap()));
+    assert!(token.is_frozen(&accounts.last().unwrap()));
+
+    freezes.push_back((Address::generate(&e), 0, reason.clone(), ComplianceStatus::Sanctioned));
+    assert_token_error(
+        token.try_freeze_accounts(&vec![&e, freezer.clone()], &freezes, &FreezeScope::Full),
+        TokenError::BatchTooLarge,
+    );
+    accounts.push_back(Address::generate(&e));
+    assert_token_error(token.try_unfreeze_accounts(&vec![&e, freezer.clone()], &accounts), TokenError::BatchTooLarge);
+
+    let outsider = Address::generate(&e);
+    assert_token_error(
+        token.try_unfreeze_accounts(&vec![&e, outsider.clone()], &to_unfreeze),
+        TokenError::MissingRole,
+    );
+}
+
+#[test]
+fn test_update_and_extend_freeze() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let user = Address::generate(&e);
+    let recipient = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    token.mint(&vec![&e, admin.clone()], &user, &100);
+
+    let start = e.ledger().sequence();
+    let reason = String::from_str(&e, "KYC eksik");
+    token.freeze_account(&vec![&e, admin.clone()], &user, &10, &reason, &ComplianceStatus::KycPending, &FreezeScope::Outbound);
+    let old_info = token.get_account_freeze_details(&user).unwrap();
+
+    let new_reason = String::from_str(&e, "AML incelemesi");
+    token.update_freeze(&vec![&e, admin.clone()], &user, &(start + 50), &new_reason, &ComplianceStatus::AmlFlagged);
+    let updated = FreezeInfo {
+        start_ledger: start,
+        expiration_ledger: start + 50,
+        expires_at: 0,
+        reason: new_reason.clone(),
+        compliance_tag: ComplianceStatus::AmlFlagged,
+        scope: FreezeScope::Outbound,
+    };
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "freeze_amended"), user.clone()).into_val(&e),
+        (admin.clone(), old_info, updated.clone()).into_val(&e),
+    );
+    assert_eq!(token.get_account_freeze_details(&user), Some(updated.clone()));
+
+    token.extend_freeze(&vec![&e, admin.clone()], &user, &25);
+    let extended = FreezeInfo { expiration_ledger: start + 75, ..updated.clone() };
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "freeze_amended"), user.clone()).into_val(&e),
+        (admin.clone(), updated, extended.clone()).into_val(&e),
+    );
+    assert_eq!(token.get_account_freeze_details(&user), Some(extended));
+
+    // The freeze stays in force throughout and the amendments are audited.
+    jump_ledgers(&e, 74);
+    assert_token_error(token.try_transfer(&user, &recipient, &1), TokenError::AccountFrozen);
+    let history = token.get_freeze_history(&user, &0, &10);
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.get(0).unwrap().end, FreezeEnd::Amended(admin.clone(), start));
+    assert_eq!(history.get(1).unwrap().end, FreezeEnd::Amended(admin.clone(), start));
+    assert_eq!(history.get(2).unwrap().end, FreezeEnd::Open);
+
+    assert_token_error(
+        token.try_update_freeze(&vec![&e, admin.clone()], &user, &e.ledger().sequence(), &new_reason, &ComplianceStatus::AmlFlagged),
+        TokenError::InvalidExpirationLedger,
+    );
+
+    // Amendments require an active freeze.
+    jump_ledgers(&e, 1);
+    assert_token_error(token.try_extend_freeze(&vec![&e, admin.clone()], &user, &10), TokenError::AccountNotFrozen);
+    assert_token_error(
+        token.try_update_freeze(&vec![&e, admin.clone()], &recipient, &0, &new_reason, &ComplianceStatus::AmlFlagged),
+        TokenError::AccountNotFrozen,
+    );
+
+    // Indefinite and timestamp-based freezes have no ledger expiry to extend,
+    // and cannot be given one that would end them sooner.
+    let now = e.ledger().sequence();
+    token.freeze_account(&vec![&e, admin.clone()], &user, &0, &reason, &ComplianceStatus::Sanctioned, &FreezeScope::Full);
+    assert_token_error(token.try_extend_freeze(&vec![&e, admin.clone()], &user, &10), TokenError::NoLedgerExpiry);
+    assert_token_error(
+        token.try_update_freeze(&vec![&e, admin.clone()], &user, &(now + 5), &reason, &ComplianceStatus::Sanctioned),
+        TokenError::InvalidExpirationLedger,
+    );
+    let expires_at = e.ledger().timestamp() + 3600;
+    token.freeze_account_until(&vec![&e, admin.clone()], &user, &expires_at, &reason, &ComplianceStatus::Sanctioned, &FreezeScope::Full);
+    assert_token_error(token.try_extend_freeze(&vec![&e, admin.clone()], &user, &10), TokenError::NoLedgerExpiry);
+
+    // A timed freeze cannot be shortened; lifting it early takes an unfreeze.
+    token.freeze_account(&vec![&e, admin.clone()], &user, &10, &reason, &ComplianceStatus::Sanctioned, &FreezeScope::Full);
+    assert_token_error(
+        token.try_update_freeze(&vec![&e, admin.clone()], &user, &(now + 1), &reason, &ComplianceStatus::Sanctioned),
+        TokenError::InvalidExpirationLedger,
+    );
+    assert_token_error(token.try_extend_freeze(&vec![&e, admin.clone()], &user, &u32::MAX), TokenError::DurationOverflow);
+    token.update_freeze(&vec![&e, admin.clone()], &user, &(now + 10), &new_reason, &ComplianceStatus::Sanctioned);
+    token.update_freeze(&vec![&e, admin.clone()], &user, &0, &new_reason, &ComplianceStatus::Sanctioned);
+    assert_eq!(token.get_account_freeze_details(&user).unwrap().expiration_ledger, 0);
+}
+
+#[test]
+fn test_scheduled_freeze_window() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let user = Address::generate(&e);
+    let cancelled = Address::generate(&e);
+    let recipient = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    token.mint(&vec![&e, admin.clone()], &user, &100);
+
+    let start = e.ledger().sequence();
+    let scheduled = FreezeInfo {
+        start_ledger: start + 10,
+        expiration_ledger: start + 30,
+        expires_at: 0,
+        reason: String::from_str(&e, "mahkeme kararı"),
+        compliance_tag: ComplianceStatus::Sanctioned,
+        scope: FreezeScope::Full,
+    };
+    token.schedule_freeze(&vec![&e, admin.clone()], &user, &scheduled);
+    let data: Vec<Val> = vec![
+        &e,
+        admin.into_val(&e),
+        user.into_val(&e),
+        (start + 10).into_val(&e),
+        (start + 30).into_val(&e),
+        scheduled.reason.into_val(&e),
+        FreezeScope::Full.into_val(&e),
+        ComplianceStatus::Sanctioned.into_val(&e),
+    ];
+    assert_last_event(
+        &e,
+        &token.address,
+        vec![
+            &e,
+            Symbol::new(&e, "freeze_acc").into_val(&e),
+            Symbol::new(&e, "admin").into_val(&e),
+            Symbol::new(&e, "account").into_val(&e),
+            Symbol::new(&e, "starts").into_val(&e),
+            Symbol::new(&e, "expires").into_val(&e),
+            Symbol::new(&e, "reason").into_val(&e),
+            Symbol::new(&e, "scope").into_val(&e),
+            Symbol::new(&e, "compliance").into_val(&e),
+        ],
+        data.into_val(&e),
+    );
+
+    // Pending: visible as scheduled, but the account can still transact.
+    assert!(!token.is_frozen(&user));
+    assert_eq!(token.get_account_freeze_details(&user), None);
+    assert_eq!(token.get_pending_freeze(&user), Some(scheduled.clone()));
+    assert_eq!(token.list_pending_freezes(&0, &10, &None).accounts.len(), 1);
+    assert_eq!(token.list_frozen_accounts(&0, &10, &None).accounts.len(), 0);
+    token.transfer(&user, &recipient, &10);
+
+    jump_ledgers(&e, 10);
+    assert!(token.is_frozen(&user));
+    assert_eq!(token.get_pending_freeze(&user), None);
+    assert_eq!(token.get_account_freeze_details(&user), Some(scheduled.clone()));
+    assert_eq!(token.list_pending_freezes(&0, &10, &None).accounts.len(), 0);
+    assert_eq!(token.list_frozen_accounts(&0, &10, &None).accounts.len(), 1);
+    assert_token_error(token.try_transfer(&recipient, &user, &1), TokenError::RecipientFrozen);
+
+    jump_ledgers(&e, 20);
+    assert!(!token.is_frozen(&user));
+    token.transfer(&user, &recipient, &10);
+
+    // A pending freeze can be cancelled before it starts.
+    let later = FreezeInfo {
+        start_ledger: e.ledger().sequence() + 5,
+        expiration_ledger: 0,
+        ..scheduled.clone()
+    };
+    token.schedule_freeze(&vec![&e, admin.clone()], &cancelled, &later);
+    token.unfreeze_account(&vec![&e, admin.clone()], &cancelled);
+    assert_eq!(token.get_pending_freeze(&cancelled), None);
+    jump_ledgers(&e, 5);
+    assert!(!token.is_frozen(&cancelled));
+
+    let in_the_past = FreezeInfo { start_ledger: e.ledger().sequence() - 1, ..later.clone() };
+    assert_token_error(
+        token.try_schedule_freeze(&vec![&e, admin.clone()], &user, &in_the_past),
+        TokenError::InvalidStartLedger,
+    );
+    let empty_window = FreezeInfo { expiration_ledger: later.start_ledger, ..later };
+    assert_token_error(
+        token.try_schedule_freeze(&vec![&e, admin.clone()], &user, &empty_window),
+        TokenError::InvalidExpirationLedger,
+    );
+
+    // A later window cannot displace a freeze that is already in effect.
+    token.freeze_account(&vec![&e, admin.clone()], &user, &0, &scheduled.reason, &ComplianceStatus::Sanctioned, &FreezeScope::Full);
+    let deferred = FreezeInfo { start_ledger: e.ledger().sequence() + 1000, expiration_ledger: 0, ..scheduled.clone() };
+    assert_token_error(
+        token.try_schedule_freeze(&vec![&e, admin.clone()], &user, &deferred),
+        TokenError::AccountAlreadyFrozen,
+    );
+    assert!(token.is_frozen(&user));
+    assert_token_error(token.try_transfer(&user, &recipient, &1), TokenError::AccountFrozen);
+}
+
+#[test]
+fn test_timestamp_based_freeze() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let user = Address::generate(&e);
+    let recipient = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    token.mint(&vec![&e, admin.clone()], &user, &100);
+    advance_time(&e, 1_700_000_000);
+
+    let one_day = 24 * 60 * 60;
+    let expires_at = e.ledger().timestamp() + one_day;
+    let reason = String::from_str(&e, "mahkeme kararı, 1 gün");
+    token.freeze_account_until(&vec![&e, admin.clone()], &user, &expires_at, &reason, &ComplianceStatus::Sanctioned, &FreezeScope::Outbound);
+    let info = token.get_account_freeze_details(&user).unwrap();
+    assert_eq!(info.expires_at, expires_at);
+    assert_eq!(info.expiration_ledger, 0);
+
+    // Ledger sequence alone does not end the freeze, however far it moves.
+    advance_sequence(&e, 2 * DAY_IN_LEDGERS);
+    assert!(token.is_frozen(&user));
+    assert_token_error(token.try_transfer(&user, &recipient, &1), TokenError::AccountFrozen);
+
+    advance_time(&e, one_day - 1);
+    assert!(token.is_frozen(&user));
+
+    advance_time(&e, 1);
+    assert!(!token.is_frozen(&user));
+    token.transfer(&user, &recipient, &1);
+    assert_eq!(
+        token.get_freeze_history(&user, &0, &1).get(0).unwrap().end,
+        FreezeEnd::ExpiredAt(expires_at),
+    );
+    assert_eq!(token.list_frozen_accounts(&0, &10, &None).accounts.len(), 0);
+
+    // A window bounded both ways ends at whichever bound comes first.
+    let both = FreezeInfo {
+        start_ledger: e.ledger().sequence(),
+        expiration_ledger: e.ledger().sequence() + 100,
+        expires_at: e.ledger().timestamp() + 60,
+        reason: reason.clone(),
+        compliance_tag: ComplianceStatus::Other,
+        scope: FreezeScope::Full,
+    };
+    token.schedule_freeze(&vec![&e, admin.clone()], &user, &both);
+    advance_sequence(&e, 50);
+    assert!(token.is_frozen(&user));
+    advance_time(&e, 60);
+    assert!(!token.is_frozen(&user));
+
+    assert_token_error(
+        token.try_freeze_account_until(&vec![&e, admin.clone()], &user, &e.ledger().timestamp(), &reason, &ComplianceStatus::Other, &FreezeScope::Full),
+        TokenError::InvalidExpirationTime,
+    );
+    let stale = FreezeInfo {
+        start_ledger: e.ledger().sequence(),
+        expiration_ledger: 0,
+        expires_at: e.ledger().timestamp(),
+        ..both
+    };
+    assert_token_error(token.try_schedule_freeze(&vec![&e, admin.clone()], &user, &stale), TokenError::InvalidExpirationTime);
+}
+
+#[test]
+fn test_compliance_registry_and_policy() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let officer = Address::generate(&e);
+    let pending = Address::generate(&e);
+    let flagged = Address::generate(&e);
+    let other = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    token.grant_role(&admin, &Role::ComplianceOfficer, &officer);
+    token.mint(&vec![&e, admin.clone()], &pending, &100);
+    token.mint(&vec![&e, admin.clone()], &flagged, &100);
+
+    let evidence = BytesN::from_array(&e, &[7; 32]);
+    token.set_compliance_status(&officer, &pending, &ComplianceStatus::KycPending, &evidence);
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "compliance_status"), pending.clone()).into_val(&e),
+        (officer.clone(), ComplianceStatus::None, ComplianceStatus::KycPending, evidence.clone()).into_val(&e),
+    );
+    token.set_compliance_status(&officer, &flagged, &ComplianceStatus::AmlFlagged, &evidence);
+
+    // The registry is independent of freezing.
+    assert!(!token.is_frozen(&pending));
+    assert_eq!(token.get_compliance_status(&pending), ComplianceStatus::KycPending);
+    assert_eq!(token.get_compliance_tag_for_account(&pending), ComplianceStatus::KycPending);
+    assert_eq!(token.get_compliance_status(&other), ComplianceStatus::None);
+
+    // Without a policy every status may transact.
+    token.transfer(&pending, &other, &10);
+
+    token.set_compliance_policy(&vec![&e, admin.clone()],
+        &ComplianceStatus::KycPending,
+        &CompliancePolicy { can_send: false, can_receive: true, send_limit: None },
+    );
+    token.set_compliance_policy(&vec![&e, admin.clone()],
+        &ComplianceStatus::AmlFlagged,
+        &CompliancePolicy { can_send: true, can_receive: true, send_limit: Some(50) },
+    );
+    token.set_compliance_policy(&vec![&e, admin.clone()],
+        &ComplianceStatus::Sanctioned,
+        &CompliancePolicy { can_send: false, can_receive: false, send_limit: None },
+    );
+
+    assert_token_error(token.try_transfer(&pending, &other, &1), TokenError::SenderNotCompliant);
+    assert_token_error(token.try_burn(&pending, &1), TokenError::SenderNotCompliant);
+    token.transfer(&other, &pending, &5);
+    token.mint(&vec![&e, admin.clone()], &pending, &5);
+
+    assert_token_error(token.try_transfer(&flagged, &other, &51), TokenError::ComplianceLimitExceeded);
+    assert_token_error(token.try_burn(&flagged, &51), TokenError::ComplianceLimitExceeded);
+    token.transfer(&flagged, &other, &50);
+
+    token.set_compliance_status(&officer, &pending, &ComplianceStatus::Sanctioned, &evidence);
+    assert_token_error(token.try_mint(&vec![&e, admin.clone()], &pending, &1), TokenError::RecipientNotCompliant);
+    assert_token_error(token.try_transfer(&other, &pending, &1), TokenError::RecipientNotCompliant);
+
+    // Clearing the status lifts the restrictions and is kept in the history.
+    let cleared = BytesN::from_array(&e, &[8; 32]);
+    token.set_compliance_status(&officer, &pending, &ComplianceStatus::None, &cleared);
+    token.transfer(&pending, &other, &1);
+    let history = token.get_compliance_history(&pending);
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.get(0).unwrap().status, ComplianceStatus::KycPending);
+    assert_eq!(history.get(1).unwrap().status, ComplianceStatus::Sanctioned);
+    let latest = history.get(2).unwrap();
+    assert_eq!(latest.status, ComplianceStatus::None);
+    assert_eq!(latest.evidence_hash, cleared);
+    assert_eq!(latest.set_by, officer);
+
+    assert_token_error(
+        token.try_set_compliance_status(&other, &flagged, &ComplianceStatus::None, &evidence),
+        TokenError::MissingRole,
+    );
+}
+
+#[test]
+fn test_allowlist_mode_gates_transfers_and_mint() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let holder = Address::generate(&e);
+    let expiring = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let outsider = Address::generate(&e);
+
+    let deployer_address = Address::generate(&e);
+    let wasm_hash = e.deployer().upload_contract_wasm(WASM_BYTES);
+    let token_id = e.deployer()
+        .with_address(deployer_address, BytesN::from_array(&e, &[4; 32]))
+        .deploy_v2(wasm_hash, ());
+    let token = TokenClient::new(&e, &token_id);
+    token.initialize_with_allowlist(
+        &admin,
+        &7,
+        &String::from_str(&e, "Security Token"),
+        &String::from_str(&e, "SEC"),
+    );
+    assert!(token.allowlist_mode());
+    assert!(!token.is_allowed(&holder));
+    assert_token_error(token.try_mint(&vec![&e, admin.clone()], &holder, &100), TokenError::RecipientNotAllowed);
+
+    token.add_to_allowlist(&admin, &vec![&e, holder.clone()], &0);
+    token.add_to_allowlist(&admin, &vec![&e, expiring.clone()], &(e.ledger().sequence() + 10));
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "allowlist_added"), expiring.clone()).into_val(&e),
+        (admin.clone(), e.ledger().sequence() + 10).into_val(&e),
+    );
+    assert!(token.is_allowed(&holder));
+    assert!(token.is_allowed(&expiring));
+
+    token.mint(&vec![&e, admin.clone()], &holder, &100);
+    token.transfer(&holder, &expiring, &10);
+    assert_token_error(token.try_transfer(&holder, &outsider, &10), TokenError::RecipientNotAllowed);
+
+    // The spender itself does not need to be listed, only both holders.
+    token.approve(&holder, &spender, &50, &(e.ledger().sequence() + 100));
+    token.transfer_from(&spender, &holder, &expiring, &10);
+    assert_token_error(
+        token.try_transfer_from(&spender, &holder, &outsider, &10),
+        TokenError::RecipientNotAllowed,
+    );
+
+    // Lapsed approvals block sending as well as receiving.
+    jump_ledgers(&e, 10);
+    assert!(!token.is_allowed(&expiring));
+    assert_token_error(token.try_transfer(&expiring, &holder, &1), TokenError::SenderNotAllowed);
+    assert_token_error(token.try_transfer(&holder, &expiring, &1), TokenError::RecipientNotAllowed);
+
+    token.remove_from_allowlist(&admin, &vec![&e, holder.clone(), outsider.clone()]);
+    assert!(!token.is_allowed(&holder));
+    assert_token_error(
+        token.try_transfer_from(&spender, &holder, &expiring, &1),
+        TokenError::SenderNotAllowed,
+    );
+
+    // Seized funds cannot be moved to an address outside the allowlist either.
+    assert_token_error(
+        token.try_forced_transfer(
+            &vec![&e, admin.clone()],
+            &holder,
+            &outsider,
+            &1,
+            &String::from_str(&e, "custody"),
+        ),
+        TokenError::RecipientNotAllowed,
+    );
+    assert_eq!(token.balance(&outsider), 0);
+
+    let mut too_many = Vec::new(&e);
+    for _ in 0..=MAX_ALLOWLIST_BATCH {
+        too_many.push_back(Address::generate(&e));
+    }
+    assert_token_error(token.try_add_to_allowlist(&admin, &too_many, &0), TokenError::BatchTooLarge);
+    assert_token_error(
+        token.try_add_to_allowlist(&outsider, &vec![&e, outsider.clone()], &0),
+        TokenError::MissingRole,
+    );
+
+    // Tokens initialized without the mode allow every holder.
+    let open_token = create_token_and_init(&e, &admin);
+    assert!(!open_token.allowlist_mode());
+    assert!(open_token.is_allowed(&outsider));
+    open_token.mint(&vec![&e, admin.clone()], &outsider, &1);
+}
+
+#[test]
+fn test_external_compliance_contract_hook() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let user1 = Address::generate(&e);
+    let user2 = Address::generate(&e);
+    let blocked = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    token.mint(&vec![&e, admin.clone()], &blocked, &100);
+
+    let compliance_id = e.register(mock_compliance::MockCompliance, ());
+    let compliance = mock_compliance::MockComplianceClient::new(&e, &compliance_id);
+    token.set_compliance_contract(&vec![&e, admin.clone()], &Some(compliance_id.clone()));
+    assert_eq!(token.compliance_contract(), Some(compliance_id.clone()));
+    compliance.deny(&blocked);
+
+    token.mint(&vec![&e, admin.clone()], &user1, &100);
+    token.transfer(&user1, &user2, &30);
+    token.approve(&user1, &spender, &20, &(e.ledger().sequence() + 100));
+    token.transfer_from(&spender, &user1, &user2, &10);
+    token.burn(&user2, &5);
+    token.burn_from(&spender, &user1, &5);
+    assert_eq!(
+        compliance.notifications(),
+        vec![
+            &e,
+            (token.address.clone(), None, Some(user1.clone()), 100_i128),
+            (token.address.clone(), Some(user1.clone()), Some(user2.clone()), 30),
+            (token.address.clone(), Some(user1.clone()), Some(user2.clone()), 10),
+            (token.address.clone(), Some(user2.clone()), None, 5),
+            (token.address.clone(), Some(user1.clone()), None, 5),
+        ]
+    );
+
+    assert_token_error(token.try_mint(&vec![&e, admin.clone()], &blocked, &1), TokenError::ComplianceContractDenied);
+    assert_token_error(token.try_transfer(&blocked, &user1, &1), TokenError::ComplianceContractDenied);
+    assert_token_error(token.try_transfer(&user1, &blocked, &1), TokenError::ComplianceContractDenied);
+    assert_token_error(
+        token.try_transfer_from(&spender, &user1, &blocked, &1),
+        TokenError::ComplianceContractDenied,
+    );
+    assert_token_error(token.try_burn(&blocked, &1), TokenError::ComplianceContractDenied);
+    assert_eq!(compliance.notifications().len(), 5);
+    assert_eq!(token.balance(&blocked), 100);
+
+    // Clearing the hook stops consulting the contract.
+    token.set_compliance_contract(&vec![&e, admin.clone()], &None);
+    assert_eq!(token.compliance_contract(), None);
+    token.transfer(&blocked, &user1, &1);
+    assert_eq!(compliance.notifications().len(), 5);
+}
+
+#[test]
+fn test_velocity_limits_over_rolling_window() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let user = Address::generate(&e);
+    let vip = Address::generate(&e);
+    let spender = Address::generate(&e);
+    let recipient = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    token.mint(&vec![&e, admin.clone()], &user, &1000);
+    token.mint(&vec![&e, admin.clone()], &vip, &1000);
+    assert_eq!(token.remaining_limit(&user), None);
+
+    let daily = VelocityLimit { amount: 100, window_ledgers: DAY_IN_LEDGERS };
+    token.set_default_velocity_limit(&vec![&e, admin.clone()], &Some(daily.clone()));
+    token.set_velocity_limit(&vec![&e, admin.clone()], &vip, &Some(VelocityLimit { amount: 500, window_ledgers: DAY_IN_LEDGERS }));
+    assert_eq!(token.velocity_limit(&user), Some(daily.clone()));
+    assert_eq!(
+        token.velocity_usage(&user),
+        Some(VelocityUsage { limit: daily.clone(), spent: 0, remaining: 100, next_release_ledger: 0 })
+    );
+    assert_eq!(token.remaining_limit(&user), Some(100));
+    assert_eq!(token.remaining_limit(&vip), Some(500));
+
+    // Transfers and burns, direct or through an allowance, share the window.
+    token.transfer(&user, &recipient, &40);
+    token.burn(&user, &10);
+    token.approve(&user, &spender, &100, &(e.ledger().sequence() + DAY_IN_LEDGERS));
+    token.transfer_from(&spender, &user, &recipient, &5);
+    token.burn_from(&spender, &user, &5);
+    assert_eq!(token.remaining_limit(&user), Some(40));
+
+    let bucket_ledgers = DAY_IN_LEDGERS / 24;
+    let first_bucket_end = (e.ledger().sequence() / bucket_ledgers + 24) * bucket_ledgers;
+    jump_ledgers(&e, DAY_IN_LEDGERS / 2);
+    token.transfer(&user, &recipient, &40);
+    assert_eq!(token.remaining_limit(&user), Some(0));
+    // A blocked transfer leaves no event behind, so clients check the usage.
+    assert_eq!(
+        token.velocity_usage(&user),
+        Some(VelocityUsage {
+            limit: daily.clone(),
+            spent: 100,
+            remaining: 0,
+            next_release_ledger: first_bucket_end,
+        })
+    );
+    assert_token_error(token.try_transfer(&user, &recipient, &1), TokenError::VelocityLimitExceeded);
+    assert_token_error(token.try_burn_from(&spender, &user, &1), TokenError::VelocityLimitExceeded);
+    token.transfer(&vip, &recipient, &500);
+
+    // The first 60 units leave the window a day later; the last 40 remain.
+    jump_ledgers(&e, DAY_IN_LEDGERS / 2 + DAY_IN_LEDGERS / 24);
+    assert_eq!(token.remaining_limit(&user), Some(60));
+    assert_token_error(token.try_transfer(&user, &recipient, &61), TokenError::VelocityLimitExceeded);
+    token.transfer(&user, &recipient, &60);
+
+    jump_ledgers(&e, DAY_IN_LEDGERS);
+    assert_eq!(token.remaining_limit(&user), Some(100));
+
+    token.set_default_velocity_limit(&vec![&e, admin.clone()], &None);
+    assert_eq!(token.remaining_limit(&user), None);
+    assert_eq!(token.velocity_usage(&user), None);
+    token.transfer(&user, &recipient, &500);
+
+    assert_token_error(
+        token.try_set_default_velocity_limit(&vec![&e, admin.clone()], &Some(VelocityLimit { amount: 1, window_ledgers: 0 })),
+        TokenError::InvalidVelocityWindow,
+    );
+    assert_token_error(
+        token.try_set_velocity_limit(&vec![&e, admin.clone()], &user, &Some(VelocityLimit { amount: -1, window_ledgers: 10 })),
+        TokenError::NegativeAmount,
+    );
+}
+
+#[test]
+fn test_escalation_rules_freeze_automatically() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let officer = Address::generate(&e);
+    let pending = Address::generate(&e);
+    let sanctioned = Address::generate(&e);
+    let recipient = Address::generate(&e);
+    let bystander = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    token.grant_role(&admin, &Role::ComplianceOfficer, &officer);
+    token.mint(&vec![&e, admin.clone()], &pending, &2000);
+    token.mint(&vec![&e, admin.clone()], &sanctioned, &1000);
+    let evidence = BytesN::from_array(&e, &[1; 32]);
+    token.set_compliance_status(&officer, &pending, &ComplianceStatus::KycPending, &evidence);
+    token.set_compliance_status(&officer, &sanctioned, &ComplianceStatus::Sanctioned, &evidence);
+
+    let large_transfer = token.add_escalation_rule(&vec![&e, admin.clone()],
+        &EscalationTrigger::SendAbove(ComplianceStatus::KycPending, 500),
+        &ComplianceStatus::AmlFlagged,
+        &FreezeScope::Outbound,
+        &0,
+        &String::from_str(&e, "KYC bekleyen hesaptan büyük transfer"),
+    );
+    let tainted_funds = token.add_escalation_rule(&vec![&e, admin.clone()],
+        &EscalationTrigger::ReceiveFrom(ComplianceStatus::Sanctioned),
+        &ComplianceStatus::AmlFlagged,
+        &FreezeScope::Full,
+        &100,
+        &String::from_str(&e, "yaptırımlı adresten gelen fon"),
+    );
+    assert_eq!((large_transfer, tainted_funds), (1, 2));
+    assert_eq!(token.get_escalation_rules().len(), 2);
+
+    // Transfers under the threshold do not trigger anything.
+    token.transfer(&pending, &bystander, &500);
+    assert!(!token.is_frozen(&pending));
+
+    // The triggering transfer completes; the sender is frozen afterwards.
+    token.transfer(&pending, &recipient, &501);
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "auto_freeze"), pending.clone()).into_val(&e),
+        large_transfer.into_val(&e),
+    );
+    assert_eq!(token.balance(&recipient), 501);
+    let info = token.get_account_freeze_details(&pending).unwrap();
+    assert_eq!(info.compliance_tag, ComplianceStatus::AmlFlagged);
+    assert_eq!(info.scope, FreezeScope::Outbound);
+    assert_eq!(info.reason, String::from_str(&e, "rule 1: KYC bekleyen hesaptan büyük transfer"));
+    assert_eq!(token.get_freeze_history(&pending, &0, &1).get(0).unwrap().frozen_by, token.address);
+    assert_token_error(token.try_transfer(&pending, &recipient, &1), TokenError::AccountFrozen);
+
+    // Receiving from a sanctioned sender flags the recipient, also via an allowance.
+    token.approve(&sanctioned, &bystander, &10, &(e.ledger().sequence() + 100));
+    token.transfer_from(&bystander, &sanctioned, &recipient, &10);
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "auto_freeze"), recipient.clone()).into_val(&e),
+        tainted_funds.into_val(&e),
+    );
+    let info = token.get_account_freeze_details(&recipient).unwrap();
+    assert_eq!(info.scope, FreezeScope::Full);
+    assert_eq!(info.expiration_ledger, e.ledger().sequence() + 100);
+    assert_eq!(info.reason, String::from_str(&e, "rule 2: yaptırımlı adresten gelen fon"));
+
+    token.remove_escalation_rule(&vec![&e, admin.clone()], &tainted_funds);
+    token.transfer(&sanctioned, &bystander, &1);
+    assert!(!token.is_frozen(&bystander));
+    assert_token_error(token.try_remove_escalation_rule(&vec![&e, admin.clone()], &tainted_funds), TokenError::EscalationRuleNotFound);
+    assert_token_error(
+        token.try_add_escalation_rule(
+            &vec![&e, admin.clone()],
+            &EscalationTrigger::ReceiveFrom(ComplianceStatus::Sanctioned),
+            &ComplianceStatus::AmlFlagged,
+            &FreezeScope::Full,
+            &0,
+            &String::from_bytes(&e, &[b'x'; 201]),
+        ),
+        TokenError::ReasonTooLong,
+    );
+}
+
+#[test]
+fn test_delegated_minters_with_quotas() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let desk = Address::generate(&e);
+    let temporary_desk = Address::generate(&e);
+    let user = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+
+    token.set_minter(&vec![&e, admin.clone()], &desk, &100, &0);
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "minter_set"), vec![&e, admin.clone()], desk.clone()).into_val(&e),
+        (100_i128, 0_u32).into_val(&e),
+    );
+    assert_eq!(token.minter_quota(&desk), 100);
+
+    token.mint_as(&desk, &user, &60);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            desk.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "mint_as"),
+                    (desk.clone(), user.clone(), 60_i128).into_val(&e),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "minter_quota"), desk.clone()).into_val(&e),
+        40_i128.into_val(&e),
+    );
+    assert_eq!(token.balance(&user), 60);
+    assert_eq!(token.total_supply(), 60);
+    assert_eq!(token.minter_quota(&desk), 40);
+
+    // The quota cannot be exceeded and is used up exactly.
+    assert_token_error(token.try_mint_as(&desk, &user, &41), TokenError::MinterQuotaExceeded);
+    token.mint_as(&desk, &user, &40);
+    assert_eq!(token.minter_quota(&desk), 0);
+    assert_token_error(token.try_mint_as(&desk, &user, &1), TokenError::MinterQuotaExceeded);
+
+    // Quotas are separate from the Minter role.
+    assert_token_error(token.try_mint(&vec![&e, desk.clone()], &user, &1), TokenError::MissingRole);
+
+    token.set_minter(&vec![&e, admin.clone()], &temporary_desk, &1000, &(e.ledger().sequence() + 10));
+    token.mint_as(&temporary_desk, &user, &100);
+    jump_ledgers(&e, 10);
+    assert_eq!(token.minter_quota(&temporary_desk), 0);
+    assert_token_error(token.try_mint_as(&temporary_desk, &user, &1), TokenError::MinterExpired);
+
+    token.set_minter(&vec![&e, admin.clone()], &desk, &10, &0);
+    token.remove_minter(&vec![&e, admin.clone()], &desk);
+    assert_eq!(token.minter_quota(&desk), 0);
+    assert_token_error(token.try_mint_as(&desk, &user, &1), TokenError::NotAMinter);
+    assert_token_error(token.try_remove_minter(&vec![&e, admin.clone()], &desk), TokenError::NotAMinter);
+    assert_token_error(
+        token.try_set_minter(&vec![&e, admin.clone()], &desk, &10, &e.ledger().sequence()),
+        TokenError::InvalidExpirationLedger,
+    );
+    assert_eq!(token.total_supply(), 200);
+}
+
+#[test]
+fn test_dual_control_proposals() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let operator = Address::generate(&e);
+    let checker_a = Address::generate(&e);
+    let checker_b = Address::generate(&e);
+    let user = Address::generate(&e);
+    let frozen = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    let reason = String::from_str(&e, "court order");
+
+    token.grant_role(&admin, &Role::Proposer, &operator);
+    token.grant_role(&admin, &Role::Approver, &operator);
+    token.grant_role(&admin, &Role::Approver, &checker_a);
+    token.grant_role(&admin, &Role::Approver, &checker_b);
+    token.mint(&vec![&e, admin.clone()], &user, &1000);
+    token.set_minter(&vec![&e, admin.clone()], &checker_a, &1000, &0);
+    token.freeze_account(&vec![&e, admin.clone()], &frozen, &0, &reason, &ComplianceStatus::AmlFlagged, &FreezeScope::Outbound);
+
+    assert_token_error(
+        token.try_propose(&operator, &ProposedAction::Mint(user.clone(), 500)),
+        GovernanceError::DualControlDisabled,
+    );
+    assert_token_error(
+        token.try_set_dual_control(&vec![&e, admin.clone()], &Some(DualControlConfig { threshold: 0, window_ledgers: 100 })),
+        GovernanceError::InvalidDualControlConfig,
+    );
+    // The threshold has to be within reach of the current approvers.
+    assert_token_error(
+        token.try_set_dual_control(&vec![&e, admin.clone()], &Some(DualControlConfig { threshold: 4, window_ledgers: 100 })),
+        GovernanceError::InvalidDualControlConfig,
+    );
+    token.set_dual_control(&vec![&e, admin.clone()], &Some(DualControlConfig { threshold: 3, window_ledgers: 100 }));
+    assert_token_error(
+        token.try_revoke_role(&admin, &Role::Approver, &checker_b),
+        GovernanceError::InvalidDualControlConfig,
+    );
+    assert_token_error(token.try_renounce_role(&checker_a, &Role::Approver), GovernanceError::InvalidDualControlConfig);
+    let config = DualControlConfig { threshold: 2, window_ledgers: 100 };
+    token.set_dual_control(&vec![&e, admin.clone()], &Some(config.clone()));
+    assert_eq!(token.dual_control(), Some(config));
+
+    // Single-key calls are refused while dual control is on.
+    assert_token_error(token.try_mint(&vec![&e, admin.clone()], &user, &1), GovernanceError::DualControlRequired);
+    assert_token_error(token.try_unfreeze_account(&vec![&e, admin.clone()], &frozen), GovernanceError::DualControlRequired);
+    assert_token_error(token.try_clawback(&vec![&e, admin.clone()], &user, &1, &reason), GovernanceError::DualControlRequired);
+    assert_token_error(token.try_mint_as(&checker_a, &user, &1), GovernanceError::DualControlRequired);
+    assert_token_error(
+        token.try_forced_transfer(&vec![&e, admin.clone()], &user, &checker_a, &1, &reason),
+        GovernanceError::DualControlRequired,
+    );
+
+    // Nor can an active freeze or lock be weakened by a single key: a
+    // replacement could end the freeze a ledger later.
+    let now = e.ledger().sequence();
+    assert_token_error(
+        token.try_freeze_account(&vec![&e, admin.clone()], &frozen, &1, &reason, &ComplianceStatus::AmlFlagged, &FreezeScope::Outbound),
+        GovernanceError::DualControlRequired,
+    );
+    assert_token_error(
+        token.try_freeze_account_until(&vec![&e, admin.clone()], &frozen, &(e.ledger().timestamp() + 1), &reason, &ComplianceStatus::AmlFlagged, &FreezeScope::Outbound),
+        GovernanceError::DualControlRequired,
+    );
+    let short_freeze = FreezeInfo {
+        start_ledger: now,
+        expiration_ledger: now + 1,
+        expires_at: 0,
+        reason: reason.clone(),
+        compliance_tag: ComplianceStatus::AmlFlagged,
+        scope: FreezeScope::Outbound,
+    };
+    assert_token_error(token.try_schedule_freeze(&vec![&e, admin.clone()], &frozen, &short_freeze), GovernanceError::DualControlRequired);
+    assert_token_error(
+        token.try_freeze_accounts(&vec![&e, admin.clone()], &vec![&e, (frozen.clone(), 1_u32, reason.clone(), ComplianceStatus::AmlFlagged)], &FreezeScope::Outbound),
+        GovernanceError::DualControlRequired,
+    );
+    assert_token_error(
+        token.try_update_freeze(&vec![&e, admin.clone()], &frozen, &(now + 1), &reason, &ComplianceStatus::AmlFlagged),
+        TokenError::InvalidExpirationLedger,
+    );
+    let lock_id = token.freeze_amount(&vec![&e, admin.clone()], &user, &100, &0, &reason, &ComplianceStatus::AmlFlagged);
+    assert_token_error(token.try_release_amount(&vec![&e, admin.clone()], &user, &lock_id), GovernanceError::DualControlRequired);
+    assert!(token.is_frozen(&frozen));
+    // Freezing an account that is not frozen yet is still a single-key call.
+    let newcomer = Address::generate(&e);
+    token.schedule_freeze(&vec![&e, admin.clone()], &newcomer, &short_freeze);
+    assert!(token.is_frozen(&newcomer));
+
+    let mint_id = token.propose(&operator, &ProposedAction::Mint(user.clone(), 500));
+    assert_eq!(mint_id, 1);
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "proposal_created"), mint_id).into_val(&e),
+        (operator.clone(), ProposedAction::Mint(user.clone(), 500), e.ledger().sequence() + 100).into_val(&e),
+    );
+    assert_token_error(token.try_approve_proposal(&operator, &mint_id), GovernanceError::ProposerCannotApprove);
+    assert_token_error(token.try_propose(&checker_a, &ProposedAction::Unfreeze(frozen.clone())), TokenError::MissingRole);
+
+    token.approve_proposal(&checker_a, &mint_id);
+    assert_eq!(token.balance(&user), 1000);
+    assert_token_error(token.try_approve_proposal(&checker_a, &mint_id), GovernanceError::AlreadyApproved);
+
+    token.approve_proposal(&checker_b, &mint_id);
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "proposal_executed"), mint_id).into_val(&e),
+        ProposedAction::Mint(user.clone(), 500).into_val(&e),
+    );
+    assert_eq!(token.balance(&user), 1500);
+    let proposal = token.get_proposal(&mint_id);
+    assert_eq!(proposal.status, ProposalStatus::Executed);
+    assert_eq!(proposal.approvals, vec![&e, checker_a.clone(), checker_b.clone()]);
+    assert_token_error(token.try_approve_proposal(&admin, &mint_id), GovernanceError::ProposalNotPending);
+
+    // Rejected and cancelled proposals cannot be approved.
+    let unfreeze_id = token.propose(&operator, &ProposedAction::Unfreeze(frozen.clone()));
+    token.reject_proposal(&checker_a, &unfreeze_id);
+    assert_eq!(token.get_proposal(&unfreeze_id).status, ProposalStatus::Rejected);
+    assert_token_error(token.try_approve_proposal(&checker_b, &unfreeze_id), GovernanceError::ProposalNotPending);
+    assert!(token.is_frozen(&frozen));
+
+    let clawback_id = token.propose(&operator, &ProposedAction::Clawback(user.clone(), 200, reason.clone()));
+    token.cancel_proposal(&clawback_id);
+    assert_eq!(
+        e.auths(),
+        std::vec![(
+            operator.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    token.address.clone(),
+                    Symbol::new(&e, "cancel_proposal"),
+                    (clawback_id,).into_val(&e),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_eq!(token.get_proposal(&clawback_id).status, ProposalStatus::Cancelled);
+    assert_token_error(token.try_cancel_proposal(&clawback_id), GovernanceError::ProposalNotPending);
+
+    // Approvals have to arrive within the window.
+    let late_id = token.propose(&operator, &ProposedAction::Clawback(user.clone(), 200, reason.clone()));
+    token.approve_proposal(&checker_a, &late_id);
+    jump_ledgers(&e, 100);
+    assert_token_error(token.try_approve_proposal(&checker_b, &late_id), GovernanceError::ProposalExpired);
+    assert_eq!(token.balance(&user), 1500);
+
+    let clawback_id = token.propose(&operator, &ProposedAction::Clawback(user.clone(), 200, reason.clone()));
+    token.approve_proposal(&checker_a, &clawback_id);
+    token.approve_proposal(&admin, &clawback_id);
+    assert_eq!(token.balance(&user), 1300);
+    assert_eq!(token.total_supply(), 1300);
+
+    let unfreeze_id = token.propose(&operator, &ProposedAction::Unfreeze(frozen.clone()));
+    token.approve_proposal(&checker_a, &unfreeze_id);
+    token.approve_proposal(&checker_b, &unfreeze_id);
+    assert!(!token.is_frozen(&frozen));
+    assert_token_error(token.try_get_proposal(&99), GovernanceError::ProposalNotFound);
+
+    let release_id = token.propose(&operator, &ProposedAction::ReleaseAmount(user.clone(), lock_id));
+    token.approve_proposal(&checker_a, &release_id);
+    token.approve_proposal(&checker_b, &release_id);
+    assert_eq!(token.locked_balance(&user), 0);
+
+    token.set_dual_control(&vec![&e, admin.clone()], &None);
+    token.mint(&vec![&e, admin.clone()], &user, &1);
+    assert_eq!(token.balance(&user), 1301);
+}
+
+#[test]
+fn test_multisig_admin_set() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let signer_a = Address::generate(&e);
+    let signer_b = Address::generate(&e);
+    let signer_c = Address::generate(&e);
+    let outsider = Address::generate(&e);
+    let desk = Address::generate(&e);
+    let user = Address::generate(&e);
+    let token = create_token_and_init(&e, &signer_a);
+    let reason = String::from_str(&e, "review");
+
+    // Every newly added signer has to authorize joining the set.
+    let signers = vec![&e, signer_a.clone(), signer_b.clone(), signer_c.clone()];
+    token.set_admin_signers(&vec![&e, signer_a.clone()], &signers, &2);
+    assert_eq!(e.auths().len(), 3);
+    assert_last_event(
+        &e,
+        &token.address,
+        (Symbol::new(&e, "admin_signers_set"), vec![&e, signer_a.clone()]).into_val(&e),
+        AdminSet { signers: signers.clone(), threshold: 2 }.into_val(&e),
+    );
+    assert_eq!(token.admin(), AdminSet { signers: signers.clone(), threshold: 2 });
+
+    assert_token_error(
+        token.try_set_max_supply(&vec![&e, signer_a.clone()], &1000, &true),
+        GovernanceError::InsufficientAdminSigners,
+    );
+    assert_token_error(
+        token.try_set_max_supply(&vec![&e, signer_a.clone(), signer_a.clone()], &1000, &true),
+        GovernanceError::DuplicateAdminSigner,
+    );
+    assert_token_error(
+        token.try_set_max_supply(&vec![&e, signer_a.clone(), outsider.clone()], &1000, &true),
+        GovernanceError::NotAnAdminSigner,
+    );
+    token.set_max_supply(&vec![&e, signer_a.clone(), signer_c.clone()], &1000, &true);
+    assert_eq!(
+        e.auths(),
+        std::vec![
+            (
+                signer_a.clone(),
+                AuthorizedInvocation {
+                    function: AuthorizedFunction::Contract((
+                        token.address.clone(),
+                        Symbol::new(&e, "set_max_supply"),
+                        (vec![&e, signer_a.clone(), signer_c.clone()], 1000_i128, true).into_val(&e),
+                    )),
+                    sub_invocations: std::vec![],
+                }
+            ),
+            (
+                signer_c.clone(),
+                AuthorizedInvocation {
+                    function: AuthorizedFunction::Contract((
+                        token.address.clone(),
+                        Symbol::new(&e, "set_max_supply"),
+                        (vec![&e, signer_a.clone(), signer_c.clone()], 1000_i128, true).into_val(&e),
+                    )),
+                    sub_invocations: std::vec![],
+                }
+            ),
+        ]
+    );
+
+    // A single key can no longer mint or freeze, even one holding the role.
+    assert_token_error(
+        token.try_mint(&vec![&e, signer_a.clone()], &user, &10),
+        GovernanceError::InsufficientAdminSigners,
+    );
+    assert_token_error(
+        token.try_freeze_account(&vec![&e, signer_b.clone()], &user, &0, &reason, &ComplianceStatus::Other, &FreezeScope::Full),
+        GovernanceError::InsufficientAdminSigners,
+    );
+    token.admin_grant_role(&vec![&e, signer_b.clone(), signer_c.clone()], &Role::Minter, &desk);
+    assert_token_error(token.try_mint(&vec![&e, desk.clone()], &user, &10), GovernanceError::NotAnAdminSigner);
+    token.mint(&vec![&e, signer_b.clone(), signer_c.clone()], &user, &10);
+    assert_eq!(token.balance(&user), 10);
+    token.admin_revoke_role(&vec![&e, signer_a.clone(), signer_b.clone()], &Role::Minter, &desk);
+
+    // The set itself must stay satisfiable.
+    let quorum = vec![&e, signer_a.clone(), signer_b.clone()];
+    assert_token_error(token.try_set_admin_threshold(&quorum, &4), GovernanceError::InvalidAdminSet);
+    assert_token_error(token.try_set_admin_threshold(&quorum, &0), GovernanceError::InvalidAdminSet);
+    assert_token_error(
+        token.try_set_admin_signers(&quorum, &vec![&e, signer_a.clone(), signer_a.clone()], &1),
+        GovernanceError::DuplicateAdminSigner,
+    );
+    assert_token_error(
+        token.try_set_admin_signers(&quorum, &vec![&e, signer_a.clone()], &2),
+        GovernanceError::InvalidAdminSet,
+    );
+    token.set_admin_threshold(&quorum, &3);
+    assert_token_error(token.try_set_admin_threshold(&quorum, &2), GovernanceError::InsufficientAdminSigners);
+
+    // Rotating out a signer takes effect immediately.
+    token.set_admin_signers(&signers, &vec![&e, signer_b.clone(), signer_c.clone()], &2);
+    assert_token_error(
+        token.try_update_max_supply(&vec![&e, signer_a.clone(), signer_b.clone()], &500),
+        GovernanceError::NotAnAdminSigner,
+    );
+    token.update_max_supply(&vec![&e, signer_b.clone(), signer_c.clone()], &500);
+    assert_eq!(token.max_supply(), Some(500));
+
+    // An M-of-N set cannot be collapsed to one key by an admin transfer.
+    let quorum = vec![&e, signer_b.clone(), signer_c.clone()];
+    assert_token_error(
+        token.try_set_admin(&quorum, &outsider),
+        GovernanceError::AdminTransferRequiresSingleSigner,
+    );
+    assert_token_error(
+        token.try_propose_admin(&quorum, &outsider, &10),
+        GovernanceError::AdminTransferRequiresSingleSigner,
+    );
+    assert_eq!(token.pending_admin(), None);
+
+    // Nor can a transfer proposed before the threshold was raised.
+    token.set_admin_threshold(&quorum, &1);
+    token.set_admin(&vec![&e, signer_b.clone()], &outsider);
+    token.set_admin_threshold(&vec![&e, signer_b.clone()], &2);
+    assert_token_error(token.try_accept_admin(), GovernanceError::AdminTransferRequiresSingleSigner);
+    assert_eq!(token.admin(), AdminSet { signers: quorum.clone(), threshold: 2 });
+
+    // Once the set agrees to a single signer, handing over restores the implicit roles.
+    token.set_admin_threshold(&quorum, &1);
+    token.accept_admin();
+    assert_eq!(token.admin(), AdminSet { signers: vec![&e, outsider.clone()], threshold: 1 });
+    token.mint(&vec![&e, outsider.clone()], &user, &5);
+    assert_eq!(token.balance(&user), 15);
+}
+
+#[test]
+#[should_panic]
+fn test_multisig_admin_requires_every_listed_signer() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let signer_a = Address::generate(&e);
+    let signer_b = Address::generate(&e);
+    let token = create_token_and_init(&e, &signer_a);
+    let signers = vec![&e, signer_a.clone(), signer_b.clone()];
+    token.set_admin_signers(&vec![&e, signer_a.clone()], &signers, &2);
+
+    // Only the first signer actually signs.
+    e.mock_auths(&[MockAuth {
+        address: &signer_a,
+        invoke: &MockAuthInvoke {
+            contract: &token.address,
+            fn_name: "set_max_supply",
+            args: (signers.clone(), 1000_i128, true).into_val(&e),
+            sub_invokes: &[],
+        },
+    }]);
+    token.set_max_supply(&signers, &1000, &true);
+}
+
+#[test]
+fn test_admin_quorum_required_for_mint_and_unfreeze() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let signer_a = Address::generate(&e);
+    let signer_b = Address::generate(&e);
+    let desk = Address::generate(&e);
+    let user = Address::generate(&e);
+    let token = create_token_and_init(&e, &signer_a);
+    let signers = vec![&e, signer_a.clone(), signer_b.clone()];
+    token.set_admin_signers(&vec![&e, signer_a.clone()], &signers, &2);
+    token.admin_grant_role(&signers, &Role::Minter, &desk);
+    token.admin_grant_role(&signers, &Role::Freezer, &desk);
+    let reason = String::from_str(&e, "review");
+    token.freeze_account(&signers, &user, &0, &reason, &ComplianceStatus::Other, &FreezeScope::Full);
+    assert_eq!(e.auths().len(), 2);
+
+    // Neither a role holder nor a single admin signer can act alone.
+    assert_token_error(token.try_mint(&vec![&e, desk.clone()], &user, &10), GovernanceError::NotAnAdminSigner);
+    assert_token_error(
+        token.try_unfreeze_account(&vec![&e, desk.clone()], &user),
+        GovernanceError::NotAnAdminSigner,
+    );
+    assert_token_error(
+        token.try_unfreeze_accounts(&vec![&e, desk.clone()], &vec![&e, user.clone()]),
+        GovernanceError::NotAnAdminSigner,
+    );
+    assert_token_error(
+        token.try_mint(&vec![&e, signer_a.clone()], &user, &10),
+        GovernanceError::InsufficientAdminSigners,
+    );
+    assert_token_error(
+        token.try_unfreeze_account(&vec![&e, signer_b.clone()], &user),
+        GovernanceError::InsufficientAdminSigners,
+    );
+    assert!(token.is_frozen(&user));
+
+    token.unfreeze_account(&signers, &user);
+    assert_eq!(e.auths().len(), 2);
+    assert!(!token.is_frozen(&user));
+    token.mint(&signers, &user, &10);
+    assert_eq!(e.auths().len(), 2);
+    assert_eq!(token.balance(&user), 10);
+}
+
+#[test]
+fn test_admin_quorum_required_for_freeze_management() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let signer_a = Address::generate(&e);
+    let signer_b = Address::generate(&e);
+    let desk = Address::generate(&e);
+    let user = Address::generate(&e);
+    let token = create_token_and_init(&e, &signer_a);
+    token.mint(&vec![&e, signer_a.clone()], &user, &100);
+    let signers = vec![&e, signer_a.clone(), signer_b.clone()];
+    token.set_admin_signers(&vec![&e, signer_a.clone()], &signers, &2);
+    token.admin_grant_role(&signers, &Role::Freezer, &desk);
+    token.admin_grant_role(&signers, &Role::ComplianceOfficer, &desk);
+
+    let now = e.ledger().sequence();
+    let reason = String::from_str(&e, "review");
+    let expires_at = e.ledger().timestamp() + 3600;
+    let freeze_info = FreezeInfo {
+        start_ledger: now,
+        expiration_ledger: now + 10,
+        expires_at: 0,
+        reason: reason.clone(),
+        compliance_tag: ComplianceStatus::AmlFlagged,
+        scope: FreezeScope::Full,
+    };
+
+    // Neither a role holder nor a single admin signer can act alone.
+    for single in [vec![&e, desk.clone()], vec![&e, signer_b.clone()]] {
+        let expected: Error = if single.contains(&desk) {
+            GovernanceError::NotAnAdminSigner.into()
+        } else {
+            GovernanceError::InsufficientAdminSigners.into()
+        };
+        assert_token_error(
+            token.try_freeze_account_until(&single, &user, &expires_at, &reason, &ComplianceStatus::AmlFlagged, &FreezeScope::Full),
+            expected,
+        );
+        assert_token_error(token.try_schedule_freeze(&single, &user, &freeze_info), expected);
+        assert_token_error(
+            token.try_freeze_amount(&single, &user, &10, &0, &reason, &ComplianceStatus::AmlFlagged),
+            expected,
+        );
+    }
+    assert!(!token.is_frozen(&user));
+
+    token.schedule_freeze(&signers, &user, &freeze_info);
+    let lock_id = token.freeze_amount(&signers, &user, &10, &0, &reason, &ComplianceStatus::AmlFlagged);
+    assert_eq!(e.auths().len(), 2);
+    for single in [vec![&e, desk.clone()], vec![&e, signer_b.clone()]] {
+        let expected: Error = if single.contains(&desk) {
+            GovernanceError::NotAnAdminSigner.into()
+        } else {
+            GovernanceError::InsufficientAdminSigners.into()
+        };
+        assert_token_error(
+            token.try_update_freeze(&single, &user, &(now + 20), &reason, &ComplianceStatus::Sanctioned),
+            expected,
+        );
+        assert_token_error(token.try_extend_freeze(&single, &user, &10), expected);
+        assert_token_error(token.try_set_compliance_tag(&single, &user, &ComplianceStatus::None), expected);
+        assert_token_error(token.try_release_amount(&single, &user, &lock_id), expected);
+    }
+    assert_eq!(token.get_account_freeze_details(&user), Some(freeze_info));
+    assert_eq!(token.locked_balance(&user), 10);
+
+    token.update_freeze(&signers, &user, &(now + 20), &reason, &ComplianceStatus::Sanctioned);
+    token.extend_freeze(&signers, &user, &10);
+    token.set_compliance_tag(&signers, &user, &ComplianceStatus::AmlFlagged);
+    token.release_amount(&signers, &user, &lock_id);
+    assert_eq!(e.auths().len(), 2);
+    let info = token.get_account_freeze_details(&user).unwrap();
+    assert_eq!(info.expiration_ledger, now + 30);
+    assert_eq!(info.compliance_tag, ComplianceStatus::AmlFlagged);
+    assert_eq!(token.locked_balance(&user), 0);
+    token.freeze_account_until(&signers, &user, &expires_at, &reason, &ComplianceStatus::Sanctioned, &FreezeScope::Full);
+    assert_eq!(token.get_account_freeze_details(&user).unwrap().expires_at, expires_at);
+}
+
+#[test]
+fn test_frozen_index_ttl_follows_freeze_record() {
+    let e = Env::default();
+    e.mock_all_auths();
+    let admin = Address::generate(&e);
+    let account = Address::generate(&e);
+    let token = create_token_and_init(&e, &admin);
+    token.freeze_account(
+        &vec![&e, admin.clone()],
+        &account,
+        &0,
+        &String::from_str(&e, "indefinite"),
+        &ComplianceStatus::Sanctioned,
+        &FreezeScope::Full,
+    );
+
+    for _ in 0..18 {
+        jump_ledgers(&e, DAY_IN_LEDGERS);
+        assert!(token.is_frozen(&account));
+    }
+
+    e.as_contract(&token.address, || {
+        let record_ttl = e
+            .storage()
+            .persistent()
+            .get_ttl(&DataKey::AccountFreezeInfo(account.clone()));
+        assert!(record_ttl > 20 * DAY_IN_LEDGERS);
+        assert_eq!(
+            e.storage().persistent().get_ttl(&DataKey::FrozenAccountSlot(account.clone())),
+            record_ttl
+        );
+        assert_eq!(e.storage().persistent().get_ttl(&DataKey::FrozenAccountAt(0)), record_ttl);
+    });
+    assert_eq!(token.list_frozen_accounts(&0, &10, &None).accounts.len(), 1);
+}
@@ -0,0 +1,50 @@
+use crate::error::TokenError;
+use crate::storage_types::{DataKey, CURRENT_SCHEMA_VERSION};
+use soroban_sdk::{panic_with_error, Env};
+
+/// Deployments that predate schema versioning are treated as version 1.
+const UNVERSIONED_SCHEMA_VERSION: u32 = 1;
+
+pub fn read_schema_version(e: &Env) -> u32 {
+    let key = DataKey::SchemaVersion;
+    e.storage()
+        .instance()
+        .get(&key)
+        .unwrap_or(UNVERSIONED_SCHEMA_VERSION)
+}
+
+pub fn write_schema_version(e: &Env, version: u32) {
+    let key = DataKey::SchemaVersion;
+    e.storage().instance().set(&key, &version);
+}
+
+// Version 1 kept freeze records in instance storage and had no supply
+// tracking. Instance keys cannot be enumerated on-chain, so individual freeze
+// records are moved with `migrate_freeze_records`, and balances cannot be
+// summed either: the supply counter is seeded from the total the admin
+// attests to, which must match the sum of existing balances.
+fn migrate_v1_to_v2(e: &Env, legacy_total_supply: Option<i128>) {
+    let key = DataKey::TotalSupply;
+    if !e.storage().instance().has(&key) {
+        let supply = legacy_total_supply
+            .unwrap_or_else(|| panic_with_error!(e, TokenError::LegacySupplyRequired));
+        if supply < 0 {
+            panic_with_error!(e, TokenError::NegativeAmount);
+        }
+        e.storage().instance().set(&key, &supply);
+    }
+}
+
+/// Runs every pending migration step in order and returns the resulting
+/// schema version. Each step is idempotent, so calling this again is a no-op.
+pub fn run_migrations(e: &Env, legacy_total_supply: Option<i128>) -> u32 {
+    let mut version = read_schema_version(e);
+    while version < CURRENT_SCHEMA_VERSION {
+        if version == 1 {
+            migrate_v1_to_v2(e, legacy_total_supply);
+        }
+        version += 1;
+        write_schema_version(e, version);
+    }
+    version
+}
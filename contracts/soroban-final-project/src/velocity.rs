@@ -0,0 +1,146 @@
+use crate::error::TokenError;
+use crate::storage_types::{
+    DataKey, OutflowBucket, OutflowCounter, VelocityLimit, VelocityUsage, BALANCE_BUMP_AMOUNT,
+    BALANCE_LIFETIME_THRESHOLD, VELOCITY_BUCKETS,
+};
+use soroban_sdk::{panic_with_error, Address, Env, Vec};
+
+// Outflows are summed in buckets of `window_ledgers / VELOCITY_BUCKETS`
+// ledgers, so the rolling window advances one bucket at a time and each
+// account keeps at most `VELOCITY_BUCKETS` counters.
+
+pub fn read_default_limit(e: &Env) -> Option<VelocityLimit> {
+    e.storage().instance().get(&DataKey::DefaultVelocityLimit)
+}
+
+pub fn write_default_limit(e: &Env, limit: &Option<VelocityLimit>) {
+    match limit {
+        Some(limit) => e.storage().instance().set(&DataKey::DefaultVelocityLimit, limit),
+        None => e.storage().instance().remove(&DataKey::DefaultVelocityLimit),
+    }
+}
+
+pub fn read_account_limit(e: &Env, addr: Address) -> Option<VelocityLimit> {
+    let key = DataKey::VelocityLimit(addr);
+    let limit = e.storage().persistent().get::<DataKey, VelocityLimit>(&key)?;
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+    Some(limit)
+}
+
+pub fn write_account_limit(e: &Env, addr: Address, limit: &Option<VelocityLimit>) {
+    let key = DataKey::VelocityLimit(addr);
+    match limit {
+        Some(limit) => {
+            e.storage().persistent().set(&key, limit);
+            e.storage()
+                .persistent()
+                .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+        }
+        None => e.storage().persistent().remove(&key),
+    }
+}
+
+/// The per-account limit if one is set, otherwise the default limit.
+pub fn effective_limit(e: &Env, addr: Address) -> Option<VelocityLimit> {
+    read_account_limit(e, addr).or_else(|| read_default_limit(e))
+}
+
+fn bucket_ledgers(limit: &VelocityLimit) -> u32 {
+    limit.window_ledgers.div_ceil(VELOCITY_BUCKETS).max(1)
+}
+
+/// Returns the buckets of `addr` that still fall inside the window of `limit`.
+fn read_window(e: &Env, addr: Address, limit: &VelocityLimit) -> Vec<OutflowBucket> {
+    let key = DataKey::Outflows(addr);
+    let Some(counter) = e.storage().persistent().get::<DataKey, OutflowCounter>(&key) else {
+        return Vec::new(e);
+    };
+    // Counters recorded under a different bucket size cannot be compared.
+    let bucket_ledgers = bucket_ledgers(limit);
+    if counter.bucket_ledgers != bucket_ledgers {
+        return Vec::new(e);
+    }
+    let current = e.ledger().sequence() / bucket_ledgers;
+    let window_buckets = limit.window_ledgers.div_ceil(bucket_ledgers);
+    let mut buckets = Vec::new(e);
+    for bucket in counter.buckets.iter() {
+        if bucket.index + window_buckets > current {
+            buckets.push_back(bucket);
+        }
+    }
+    buckets
+}
+
+fn spent_in_window(buckets: &Vec<OutflowBucket>) -> i128 {
+    buckets
+        .iter()
+        .fold(0i128, |total, bucket| total.saturating_add(bucket.amount))
+}
+
+/// The outflow of `addr` in the current window of its limit, or `None`
+/// without a limit.
+pub fn read_usage(e: &Env, addr: Address) -> Option<VelocityUsage> {
+    let limit = effective_limit(e, addr.clone())?;
+    let buckets = read_window(e, addr, &limit);
+    let spent = spent_in_window(&buckets);
+    let bucket_ledgers = bucket_ledgers(&limit);
+    let window_buckets = limit.window_ledgers.div_ceil(bucket_ledgers);
+    let next_release_ledger = buckets.first().map_or(0, |oldest| {
+        oldest
+            .index
+            .saturating_add(window_buckets)
+            .saturating_mul(bucket_ledgers)
+    });
+    Some(VelocityUsage {
+        remaining: (limit.amount - spent).max(0),
+        limit,
+        spent,
+        next_release_ledger,
+    })
+}
+
+/// How much `addr` can still send in the current window, or `None` without a
+/// limit.
+pub fn remaining_limit(e: &Env, addr: Address) -> Option<i128> {
+    read_usage(e, addr).map(|usage| usage.remaining)
+}
+
+/// Records an outflow of `amount` from `addr`, failing with
+/// `VelocityLimitExceeded` if it would exceed the limit for the window.
+pub fn record_outflow(e: &Env, addr: Address, amount: i128) {
+    let Some(limit) = effective_limit(e, addr.clone()) else {
+        return;
+    };
+    let mut buckets = read_window(e, addr.clone(), &limit);
+    let spent = spent_in_window(&buckets);
+    if spent.saturating_add(amount) > limit.amount {
+        panic_with_error!(e, TokenError::VelocityLimitExceeded);
+    }
+
+    let bucket_ledgers = bucket_ledgers(&limit);
+    let current = e.ledger().sequence() / bucket_ledgers;
+    match buckets.last() {
+        Some(mut last) if last.index == current => {
+            last.amount += amount;
+            buckets.set(buckets.len() - 1, last);
+        }
+        _ => buckets.push_back(OutflowBucket {
+            index: current,
+            amount,
+        }),
+    }
+
+    let key = DataKey::Outflows(addr);
+    e.storage().persistent().set(
+        &key,
+        &OutflowCounter {
+            bucket_ledgers,
+            buckets,
+        },
+    );
+    e.storage()
+        .persistent()
+        .extend_ttl(&key, BALANCE_LIFETIME_THRESHOLD, BALANCE_BUMP_AMOUNT);
+}
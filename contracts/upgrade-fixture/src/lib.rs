@@ -0,0 +1,47 @@
+#![no_std]
+
+//! Stand-in for a future build of the token, used by the token's upgrade
+//! tests. It reads the same storage layout and can upgrade back.
+
+use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Vec};
+
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Balance(Address),
+    AdminSet,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub struct AdminSet {
+    pub signers: Vec<Address>,
+    pub threshold: u32,
+}
+
+pub const FIXTURE_VERSION: u32 = 99;
+
+#[contract]
+pub struct UpgradeFixture;
+
+#[contractimpl]
+impl UpgradeFixture {
+    pub fn version(_e: Env) -> u32 {
+        FIXTURE_VERSION
+    }
+
+    pub fn balance(e: Env, id: Address) -> i128 {
+        e.storage()
+            .persistent()
+            .get(&DataKey::Balance(id))
+            .unwrap_or(0)
+    }
+
+    pub fn upgrade(e: Env, new_wasm_hash: BytesN<32>) {
+        let admin_set: AdminSet = e.storage().instance().get(&DataKey::AdminSet).unwrap();
+        for signer in admin_set.signers.iter() {
+            signer.require_auth();
+        }
+        e.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+}